@@ -1 +1,14 @@
 app-title = Text Wrench
+
+error-invalid-input = The input is not valid for this operation
+error-invalid-input-message = Invalid input: { $message }
+error-invalid-input-at-offset = Invalid input at offset { $offset }
+error-invalid-input-in-range = Invalid input from offset { $start } to { $end }: { $message }
+error-in-step = Step { $step } ({ $operation }) failed: { $message }
+
+recipe-error-io = Unable to access the recipe file: { $message }
+recipe-error-parse = Unable to read the recipe file: { $message }
+recipe-error-newer-version = The recipe file was saved by a newer version of Text Wrench and cannot be changed by this version
+recipe-newer-version = The recipe file was saved by a newer version of Text Wrench, some recipes may not load correctly
+recipe-unknown-operations = Recipe "{ $name }" uses unknown operations that were skipped: { $operations }
+recipe-copy-name = { $name } (copy)
@@ -1,7 +1,17 @@
 use std::hash::DefaultHasher;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::operations::text_operations::{TextOperation, TextOperations};
+use crate::core::error_highlighter::{self, ErrorHighlightSettings, ErrorHighlighter};
+use crate::core::recipes::{Recipe, RecipeBook};
+use crate::operations::magic::{self, Suggestion};
+use crate::operations::pipeline::{self, PipelineStep, StepOutput};
+use crate::operations::text_operations::{
+    DataType, OperationParameters, ParameterKind, ParameterValue, TextOperation,
+    TextOperationResult, TextOperations,
+};
 use crate::{fl, operations};
 use cosmic::cosmic_theme::palette::convert::IntoColorUnclamped;
 use cosmic::cosmic_theme::palette::num::Ln;
@@ -23,6 +33,9 @@ use cosmic::{
 
 const DEFAULT_PADDING: f32 = 1.;
 
+/// How long the input and operations have to stay unchanged before a conversion starts
+const CONVERSION_DEBOUNCE: Duration = Duration::from_millis(150);
+
 /// This is the struct that represents your application.
 /// It is used to define the data that will be used by your application.
 #[derive(Clone, Default)]
@@ -32,11 +45,67 @@ pub struct YourApp {
     core: Core,
     content_to_convert: TextContent,
     converted_content: TextContent,
+    /// Set when the output is bytes that are not UTF-8 and are shown as hex, which cannot be
+    /// put back into the input
+    output_is_binary: bool,
+    conversion_error: Option<String>,
+    failed_step: Option<usize>,
+    step_outputs: Vec<StepOutput>,
+    input_error_highlight: ErrorHighlightSettings,
     requires_conversion: bool,
-    selected_operations: Vec<usize>,
+    /// Increases every time a conversion is scheduled so results of superseded runs are ignored
+    conversion_generation: u64,
+    /// Set to stop the conversion that is currently running
+    conversion_cancelled: Arc<AtomicBool>,
+    converting: bool,
+    selected_operations: Vec<SelectedOperation>,
     operation_names: Vec<&'static str>,
     operation_ids: Vec<&'static str>,
     operation_none_index: usize,
+    recipes: RecipeBook,
+    recipe_name: String,
+    renaming_recipe: Option<(usize, String)>,
+    /// The text typed into an integer parameter, kept until it is submitted so it can be
+    /// cleared or pass through values outside the range while typing
+    editing_integer: Option<(usize, &'static str, String)>,
+    recipe_warning: Option<String>,
+    suggestions: Vec<Suggestion>,
+}
+
+/// An operation chosen in the conversion list along with the parameters set for it
+#[derive(Clone)]
+pub struct SelectedOperation {
+    operation_index: usize,
+    parameters: OperationParameters,
+    show_output: bool,
+}
+
+impl SelectedOperation {
+    fn new(operation_index: usize) -> Self {
+        SelectedOperation {
+            operation_index,
+            parameters: OperationParameters::default(),
+            show_output: false,
+        }
+    }
+}
+
+/// The result of running the selected operations on the input text
+#[derive(Debug, Clone)]
+pub struct ConversionOutcome {
+    output: String,
+    output_is_binary: bool,
+    error: Option<ConversionFailure>,
+    step_outputs: Vec<StepOutput>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConversionFailure {
+    step: usize,
+    message: String,
+    /// The byte range of the input text that caused the failure, only set when the failing
+    /// step received the input text unchanged
+    input_range: Option<Range<usize>>,
 }
 
 pub struct TextContent {
@@ -72,17 +141,33 @@ impl Clone for TextContent {
 /// If your application does not need to send messages, you can use an empty enum or `()`.
 #[derive(Debug, Clone)]
 pub enum Message {
-    TickSlow,
+    StartConversion(u64),
     InputContentEditorAction(text_editor::Action),
     SetInputContent(String),
     ClearInputContent,
     CopyInputContent,
     PasteInputContent,
     ConvertedContentEditorAction(text_editor::Action),
-    SetConvertedContent(String),
+    ConversionComplete(u64, Option<ConversionOutcome>),
     CopyConvertedContent,
     DeleteOperation(usize),
     SelectOperation(usize, usize),
+    SetOperationParameter(usize, &'static str, ParameterValue),
+    IntegerParameterInput(usize, &'static str, String),
+    FinishIntegerParameter,
+    ReverseOperations,
+    ToggleStepOutput(usize),
+    RecipeNameInput(String),
+    SaveRecipe,
+    LoadRecipe(usize),
+    StartRenameRecipe(usize),
+    RenameRecipeInput(String),
+    FinishRenameRecipe,
+    DuplicateRecipe(usize),
+    DeleteRecipe(usize),
+    AnalyseInput,
+    SuggestionsReady(Vec<Suggestion>),
+    ApplySuggestion(usize),
 }
 
 /// Implement the `Application` trait for your application.
@@ -115,31 +200,27 @@ impl Application for YourApp {
         vec![widget::text::text(fl!("app-title")).into()]
     }
 
-    fn subscription(&self) -> cosmic::iced::Subscription<Self::Message> {
-        cosmic::iced::time::every(Duration::from_millis(100)).map(|_| Message::TickSlow)
-    }
-
     fn update(
         &mut self,
         message: Self::Message,
     ) -> cosmic::iced::Command<app::Message<Self::Message>> {
-        match message {
-            Message::TickSlow => {
-                //Command::none()
-                if self.requires_conversion == true {
-                    self.requires_conversion = false;
-                    let selected_ids = self
-                        .selected_operations
-                        .iter()
-                        .map(|index| String::from(self.operation_ids[*index]))
-                        .collect();
-                    Command::perform(
-                        perform_conversions(self.content_to_convert.content.text(), selected_ids),
-                        |result| cosmic::app::Message::App(Message::SetConvertedContent(result)),
-                    )
-                } else {
-                    Command::none()
+        let command = match message {
+            Message::StartConversion(generation) => {
+                if generation != self.conversion_generation {
+                    return Command::none();
                 }
+
+                self.converting = true;
+                Command::perform(
+                    perform_conversions(
+                        self.content_to_convert.content.text(),
+                        self.selected_steps(),
+                        self.conversion_cancelled.clone(),
+                    ),
+                    move |outcome| {
+                        cosmic::app::Message::App(Message::ConversionComplete(generation, outcome))
+                    },
+                )
             }
             Message::SetInputContent(text) => {
                 self.content_to_convert.content = text_editor::Content::with_text(text.as_str());
@@ -147,13 +228,20 @@ impl Application for YourApp {
                 Command::none()
             }
             Message::InputContentEditorAction(action) => {
+                // moving the cursor or selecting text does not need a new conversion
+                self.requires_conversion = action.is_edit();
                 self.content_to_convert.content.perform(action);
-                self.requires_conversion = true;
                 Command::none()
             }
             Message::ClearInputContent => {
+                self.cancel_conversion();
                 self.content_to_convert.clear();
                 self.converted_content.clear();
+                self.output_is_binary = false;
+                self.conversion_error = None;
+                self.failed_step = None;
+                self.step_outputs.clear();
+                self.input_error_highlight = ErrorHighlightSettings::default();
                 Command::none()
             }
             Message::CopyConvertedContent => {
@@ -171,14 +259,46 @@ impl Application for YourApp {
                 }
                 Command::none()
             }
-            Message::SetConvertedContent(text) => {
-                self.converted_content.content = text_editor::Content::with_text(&text);
+            Message::ConversionComplete(generation, outcome) => {
+                if generation != self.conversion_generation {
+                    return Command::none();
+                }
+
+                self.converting = false;
+                let outcome = match outcome {
+                    Some(outcome) => outcome,
+                    None => return Command::none(),
+                };
+                self.converted_content.content = text_editor::Content::with_text(&outcome.output);
+                self.output_is_binary = outcome.output_is_binary;
+                self.input_error_highlight = match outcome
+                    .error
+                    .as_ref()
+                    .and_then(|error| error.input_range.clone())
+                {
+                    Some(range) => ErrorHighlightSettings::from_range(
+                        &self.content_to_convert.content.text(),
+                        range,
+                    ),
+                    None => ErrorHighlightSettings::default(),
+                };
+                self.failed_step = outcome.error.as_ref().map(|error| error.step);
+                self.conversion_error = outcome.error.map(|error| error.message);
+                self.step_outputs = outcome.step_outputs;
+                Command::none()
+            }
+            Message::ToggleStepOutput(select_index) => {
+                if let Some(selected) = self.selected_operations.get_mut(select_index) {
+                    selected.show_output = !selected.show_output;
+                }
                 Command::none()
             }
             Message::DeleteOperation(operation_index) => {
                 if operation_index < self.selected_operations.len() {
+                    self.editing_integer = None;
                     if operation_index == self.selected_operations.len() - 1 {
-                        self.selected_operations[operation_index] = self.operation_none_index;
+                        self.selected_operations[operation_index] =
+                            SelectedOperation::new(self.operation_none_index);
                     } else {
                         self.selected_operations.remove(operation_index);
                     }
@@ -187,17 +307,26 @@ impl Application for YourApp {
                 Command::none()
             }
             Message::SelectOperation(select_index, operation_index) => {
-                self.selected_operations[select_index] = operation_index;
+                if self.selected_operations[select_index].operation_index != operation_index {
+                    self.editing_integer = None;
+                    self.selected_operations[select_index] =
+                        SelectedOperation::new(operation_index);
+                }
                 self.requires_conversion = true;
 
-                if (*self.selected_operations.last().unwrap() != self.operation_none_index) {
-                    self.selected_operations.push(self.operation_none_index)
+                if (self.selected_operations.last().unwrap().operation_index
+                    != self.operation_none_index)
+                {
+                    self.selected_operations
+                        .push(SelectedOperation::new(self.operation_none_index))
                 } else {
                     while (self.selected_operations.len() > 2) {
                         let last = self.selected_operations.len() - 1;
                         let second_last = self.selected_operations.len() - 2;
-                        if (self.selected_operations[last] == self.operation_none_index
-                            && self.selected_operations[second_last] == self.operation_none_index)
+                        if (self.selected_operations[last].operation_index
+                            == self.operation_none_index
+                            && self.selected_operations[second_last].operation_index
+                                == self.operation_none_index)
                         {
                             self.selected_operations.pop();
                         } else {
@@ -207,7 +336,142 @@ impl Application for YourApp {
                 }
                 Command::none()
             }
+            Message::SetOperationParameter(select_index, parameter_id, value) => {
+                self.finish_integer_parameter();
+                if let Some(selected) = self.selected_operations.get_mut(select_index) {
+                    selected.parameters.set(parameter_id, value);
+                    self.requires_conversion = true;
+                }
+                Command::none()
+            }
+            Message::IntegerParameterInput(select_index, parameter_id, text) => {
+                match &mut self.editing_integer {
+                    Some((editing_index, editing_id, editing_text))
+                        if *editing_index == select_index && *editing_id == parameter_id =>
+                    {
+                        *editing_text = text;
+                    }
+                    _ => {
+                        self.finish_integer_parameter();
+                        self.editing_integer = Some((select_index, parameter_id, text));
+                    }
+                }
+                Command::none()
+            }
+            Message::FinishIntegerParameter => {
+                self.finish_integer_parameter();
+                Command::none()
+            }
+            Message::ReverseOperations => {
+                if let Some(reversed_steps) = pipeline::reverse_steps(&self.selected_steps()) {
+                    self.content_to_convert.content =
+                        text_editor::Content::with_text(&self.converted_content.content.text());
+                    self.select_steps(reversed_steps);
+                }
+                Command::none()
+            }
+            Message::RecipeNameInput(name) => {
+                self.recipe_name = name;
+                Command::none()
+            }
+            Message::SaveRecipe => {
+                let name = String::from(self.recipe_name.trim());
+                if !name.is_empty() {
+                    let steps = self
+                        .selected_steps()
+                        .into_iter()
+                        .filter(|step| {
+                            step.operation_id != self.operation_ids[self.operation_none_index]
+                        })
+                        .collect();
+                    self.recipes.recipes.push(Recipe { name, steps });
+                    self.recipe_name.clear();
+                    self.save_recipes();
+                }
+                Command::none()
+            }
+            Message::LoadRecipe(recipe_index) => {
+                if let Some(recipe) = self.recipes.recipes.get(recipe_index) {
+                    let (steps, unknown_ids) = recipe.resolve_steps();
+                    self.recipe_warning = match unknown_ids.is_empty() {
+                        true => None,
+                        false => Some(fl!(
+                            "recipe-unknown-operations",
+                            name = recipe.name.as_str(),
+                            operations = unknown_ids.join(", ")
+                        )),
+                    };
+                    self.select_steps(steps);
+                }
+                Command::none()
+            }
+            Message::StartRenameRecipe(recipe_index) => {
+                if let Some(recipe) = self.recipes.recipes.get(recipe_index) {
+                    self.renaming_recipe = Some((recipe_index, recipe.name.clone()));
+                }
+                Command::none()
+            }
+            Message::RenameRecipeInput(name) => {
+                if let Some((_, new_name)) = &mut self.renaming_recipe {
+                    *new_name = name;
+                }
+                Command::none()
+            }
+            Message::FinishRenameRecipe => {
+                if let Some((recipe_index, name)) = self.renaming_recipe.take() {
+                    let name = String::from(name.trim());
+                    if let Some(recipe) = self.recipes.recipes.get_mut(recipe_index) {
+                        if !name.is_empty() {
+                            recipe.name = name;
+                            self.save_recipes();
+                        }
+                    }
+                }
+                Command::none()
+            }
+            Message::DuplicateRecipe(recipe_index) => {
+                if let Some(recipe) = self.recipes.recipes.get(recipe_index) {
+                    let duplicate = Recipe {
+                        name: fl!("recipe-copy-name", name = recipe.name.as_str()),
+                        steps: recipe.steps.clone(),
+                    };
+                    self.recipes.recipes.insert(recipe_index + 1, duplicate);
+                    self.renaming_recipe = None;
+                    self.save_recipes();
+                }
+                Command::none()
+            }
+            Message::DeleteRecipe(recipe_index) => {
+                if recipe_index < self.recipes.recipes.len() {
+                    self.recipes.recipes.remove(recipe_index);
+                    self.renaming_recipe = None;
+                    self.save_recipes();
+                }
+                Command::none()
+            }
+            Message::AnalyseInput => Command::perform(
+                analyse_input(self.content_to_convert.content.text()),
+                |suggestions| cosmic::app::Message::App(Message::SuggestionsReady(suggestions)),
+            ),
+            Message::SuggestionsReady(suggestions) => {
+                self.suggestions = suggestions;
+                Command::none()
+            }
+            Message::ApplySuggestion(suggestion_index) => {
+                if suggestion_index < self.suggestions.len() {
+                    let suggestion = self.suggestions.remove(suggestion_index);
+                    self.suggestions.clear();
+                    self.select_steps(suggestion.steps);
+                }
+                Command::none()
+            }
+        };
+
+        if self.requires_conversion {
+            self.requires_conversion = false;
+            return Command::batch([command, self.schedule_conversion()]);
         }
+        command
     }
 
     /// This is the entry point of your application, it is where you initialize your application.
@@ -237,15 +501,37 @@ impl Application for YourApp {
             }
         }
 
+        let (recipes, recipe_warning) = match RecipeBook::load() {
+            Ok(recipes) if recipes.is_newer_version() => {
+                (recipes, Some(fl!("recipe-newer-version")))
+            }
+            Ok(recipes) => (recipes, None),
+            Err(e) => (RecipeBook::default(), Some(e.to_string())),
+        };
+
         let example = YourApp {
             core,
             content_to_convert: TextContent::default(),
             converted_content: TextContent::default(),
+            output_is_binary: false,
+            conversion_error: None,
+            failed_step: None,
+            step_outputs: vec![],
+            input_error_highlight: ErrorHighlightSettings::default(),
             requires_conversion: false,
-            selected_operations: vec![operation_none_index],
+            conversion_generation: 0,
+            conversion_cancelled: Arc::new(AtomicBool::new(false)),
+            converting: false,
+            selected_operations: vec![SelectedOperation::new(operation_none_index)],
             operation_ids,
             operation_names,
             operation_none_index,
+            recipes,
+            recipe_name: String::new(),
+            renaming_recipe: None,
+            editing_integer: None,
+            recipe_warning,
+            suggestions: vec![],
         };
 
         (example, Command::none())
@@ -283,24 +569,145 @@ impl Application for YourApp {
 }
 
 impl YourApp {
+    fn selected_steps(&self) -> Vec<PipelineStep> {
+        self.selected_operations
+            .iter()
+            .map(|selected| {
+                PipelineStep::new(
+                    self.operation_ids[selected.operation_index],
+                    selected.parameters.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Stops the running conversion and makes sure the result of any earlier run is ignored
+    fn cancel_conversion(&mut self) {
+        self.conversion_cancelled.store(true, Ordering::Relaxed);
+        self.conversion_cancelled = Arc::new(AtomicBool::new(false));
+        self.conversion_generation += 1;
+        self.converting = false;
+    }
+
+    /// Starts a conversion once nothing has changed for `CONVERSION_DEBOUNCE`, superseding any
+    /// conversion that is already scheduled or running
+    fn schedule_conversion(&mut self) -> Command<app::Message<Message>> {
+        self.cancel_conversion();
+        let generation = self.conversion_generation;
+
+        Command::perform(tokio::time::sleep(CONVERSION_DEBOUNCE), move |_| {
+            cosmic::app::Message::App(Message::StartConversion(generation))
+        })
+    }
+
+    /// Sets the integer parameter being typed in, clamped to its range, text that is not a
+    /// number leaves the parameter as it was
+    fn finish_integer_parameter(&mut self) {
+        let Some((select_index, parameter_id, text)) = self.editing_integer.take() else {
+            return;
+        };
+        let Some(selected) = self.selected_operations.get_mut(select_index) else {
+            return;
+        };
+        let operation = TextOperations::get_instance()
+            .get_operation_or_noop(self.operation_ids[selected.operation_index]);
+        let range = operation
+            .get_parameters()
+            .iter()
+            .find_map(|parameter| match parameter.kind {
+                ParameterKind::Integer { min, max, .. } if parameter.id == parameter_id => {
+                    Some((min, max))
+                }
+                _ => None,
+            });
+
+        if let (Some((min, max)), Ok(value)) = (range, text.trim().parse::<i64>()) {
+            selected
+                .parameters
+                .set(parameter_id, ParameterValue::Integer(value.clamp(min, max)));
+            self.requires_conversion = true;
+        }
+    }
+
+    /// Replaces the selected operations with the given steps, skipping any that are not registered
+    fn select_steps(&mut self, steps: Vec<PipelineStep>) {
+        let mut selected_operations: Vec<SelectedOperation> = steps
+            .into_iter()
+            .filter_map(|step| {
+                let operation_index = self
+                    .operation_ids
+                    .iter()
+                    .position(|id| *id == step.operation_id)?;
+                Some(SelectedOperation {
+                    operation_index,
+                    parameters: step.parameters,
+                    show_output: false,
+                })
+            })
+            .collect();
+        selected_operations.push(SelectedOperation::new(self.operation_none_index));
+
+        self.selected_operations = selected_operations;
+        self.editing_integer = None;
+        self.requires_conversion = true;
+    }
+
     fn create_conversion_options(&self) -> Element<Message> {
-        let mut operation_selection_list =
-            widget::column().push(widget::text::heading("Conversion Operations"));
+        // reversing is only offered when there is something to reverse, every step has an inverse
+        // and the output can be used as input text
+        let can_reverse = !self.output_is_binary
+            && self
+                .selected_operations
+                .iter()
+                .any(|selected| selected.operation_index != self.operation_none_index)
+            && pipeline::reverse_steps(&self.selected_steps()).is_some();
+
+        let operations_reverse =
+            widget::button::icon(widget::icon::from_name("object-flip-vertical-symbolic"))
+                .tooltip("Reverse operations")
+                .on_press_maybe(can_reverse.then_some(Message::ReverseOperations));
+        let operations_analyse =
+            widget::button::icon(widget::icon::from_name("system-search-symbolic"))
+                .tooltip("Suggest operations to decode the input")
+                .on_press(Message::AnalyseInput);
+
+        let operations_heading = widget::row()
+            .spacing(2.)
+            .align_items(cosmic::iced::Alignment::Center)
+            .push(widget::text::heading("Conversion Operations").width(Length::Fill))
+            .push(operations_analyse)
+            .push(operations_reverse);
+
+        let mut operation_selection_list = widget::column()
+            .push(operations_heading)
+            .push(self.create_suggestions());
 
         for index in 0..self.selected_operations.len() {
             let operation_dropdown = widget::dropdown(
                 &self.operation_names,
-                Some(self.selected_operations[index]),
+                Some(self.selected_operations[index].operation_index),
                 move |operation_index| Message::SelectOperation(index, operation_index.clone()),
             );
 
             let mut operation_line = widget::row().spacing(2.).push(operation_dropdown);
 
+            if self.selected_operations[index].operation_index != self.operation_none_index {
+                let output_icon = match self.selected_operations[index].show_output {
+                    true => "pan-down-symbolic",
+                    false => "pan-end-symbolic",
+                };
+                let operation_output = widget::button::icon(widget::icon::from_name(output_icon))
+                    .tooltip("Show step output")
+                    .on_press(Message::ToggleStepOutput(index));
+
+                operation_line = operation_line.push(operation_output);
+            }
+
             let add_delete = if (index == self.selected_operations.len() - 1
                 || self.selected_operations.len() == 1)
             {
                 // only add delete when there is a single or or when this is the last row and it is already none
-                self.selected_operations[index] != self.operation_none_index
+                self.selected_operations[index].operation_index != self.operation_none_index
             } else {
                 true
             };
@@ -313,10 +720,15 @@ impl YourApp {
                 operation_line = operation_line.push(operation_delete);
             }
 
-            operation_selection_list = operation_selection_list.push(operation_line);
+            operation_selection_list = operation_selection_list
+                .push(operation_line)
+                .push(self.create_parameter_options(index))
+                .push(self.create_step_output(index));
         }
 
-        widget::container(operation_selection_list)
+        operation_selection_list = operation_selection_list.push(self.create_recipe_options());
+
+        widget::container(widget::scrollable(operation_selection_list))
             .padding(10.)
             .height(Length::Fill)
             .width(Length::Fixed(300.))
@@ -325,6 +737,215 @@ impl YourApp {
             .into()
     }
 
+    fn create_suggestions(&self) -> Element<Message> {
+        let mut suggestion_list = widget::column().spacing(2.).padding([0., 0., 10., 0.]);
+
+        for (index, suggestion) in self.suggestions.iter().enumerate() {
+            let text_operations = TextOperations::get_instance();
+            let chain = suggestion
+                .steps
+                .iter()
+                .map(|step| {
+                    text_operations
+                        .get_operation_or_noop(&step.operation_id)
+                        .get_name()
+                })
+                .collect::<Vec<&str>>()
+                .join(" → ");
+
+            let suggestion_content = widget::column().push(widget::text::body(chain)).push(
+                widget::text::caption(suggestion.preview.as_str())
+                    .font(cosmic::font::FONT_MONO_REGULAR),
+            );
+
+            suggestion_list = suggestion_list.push(
+                widget::button::custom(suggestion_content)
+                    .on_press(Message::ApplySuggestion(index))
+                    .width(Length::Fill),
+            );
+        }
+
+        suggestion_list.into()
+    }
+
+    fn create_step_output(&self, index: usize) -> Element<Message> {
+        let mut step_output = widget::column().spacing(2.).padding([0., 0., 0., 10.]);
+
+        if self.failed_step == Some(index) {
+            if let Some(error) = &self.conversion_error {
+                step_output = step_output.push(
+                    widget::text::body(error.as_str())
+                        .style(cosmic::theme::Text::Color(error_highlighter::ERROR_COLOR)),
+                );
+            }
+        } else if self.selected_operations[index].show_output {
+            if let Some(output) = self.step_outputs.get(index) {
+                let output_type = match output.output_type {
+                    DataType::Text => "Text",
+                    DataType::Bytes => "Bytes",
+                };
+                let summary = format!(
+                    "{} · {} bytes · {:.2} ms",
+                    output_type,
+                    output.size,
+                    output.duration.as_secs_f64() * 1000.
+                );
+
+                step_output = step_output.push(widget::text::caption(summary)).push(
+                    widget::container(
+                        widget::text::body(output.preview.as_str())
+                            .font(cosmic::font::FONT_MONO_REGULAR),
+                    )
+                    .padding(4.)
+                    .width(Length::Fill)
+                    .style(cosmic::theme::Container::Background),
+                );
+            }
+        }
+
+        step_output.into()
+    }
+
+    fn save_recipes(&mut self) {
+        self.recipe_warning = self.recipes.save().err().map(|e| e.to_string());
+    }
+
+    fn create_recipe_options(&self) -> Element<Message> {
+        let recipe_save = widget::button::icon(widget::icon::from_name("document-save-symbolic"))
+            .tooltip("Save operations as recipe")
+            .on_press_maybe((!self.recipe_name.trim().is_empty()).then_some(Message::SaveRecipe));
+
+        let recipe_name_line = widget::row()
+            .spacing(2.)
+            .align_items(cosmic::iced::Alignment::Center)
+            .push(
+                widget::text_input("Recipe name", self.recipe_name.as_str())
+                    .on_input(Message::RecipeNameInput)
+                    .on_submit(Message::SaveRecipe),
+            )
+            .push(recipe_save);
+
+        let mut recipe_list = widget::column()
+            .spacing(2.)
+            .padding([10., 0., 0., 0.])
+            .push(widget::text::heading("Recipes"))
+            .push(recipe_name_line);
+
+        if let Some(warning) = &self.recipe_warning {
+            recipe_list = recipe_list.push(
+                widget::text::body(warning.as_str())
+                    .style(cosmic::theme::Text::Color(error_highlighter::ERROR_COLOR)),
+            );
+        }
+
+        for (index, recipe) in self.recipes.recipes.iter().enumerate() {
+            let recipe_label: Element<Message> = match &self.renaming_recipe {
+                Some((renaming_index, name)) if *renaming_index == index => {
+                    widget::text_input("Recipe name", name.as_str())
+                        .on_input(Message::RenameRecipeInput)
+                        .on_submit(Message::FinishRenameRecipe)
+                        .width(Length::Fill)
+                        .into()
+                }
+                _ => widget::button::text(recipe.name.as_str())
+                    .on_press(Message::LoadRecipe(index))
+                    .width(Length::Fill)
+                    .into(),
+            };
+
+            let recipe_rename = widget::button::icon(widget::icon::from_name("edit-symbolic"))
+                .tooltip("Rename")
+                .on_press(Message::StartRenameRecipe(index));
+            let recipe_duplicate =
+                widget::button::icon(widget::icon::from_name("edit-copy-symbolic"))
+                    .tooltip("Duplicate")
+                    .on_press(Message::DuplicateRecipe(index));
+            let recipe_delete =
+                widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                    .tooltip("Delete")
+                    .on_press(Message::DeleteRecipe(index));
+
+            recipe_list = recipe_list.push(
+                widget::row()
+                    .spacing(2.)
+                    .align_items(cosmic::iced::Alignment::Center)
+                    .push(recipe_label)
+                    .push(recipe_rename)
+                    .push(recipe_duplicate)
+                    .push(recipe_delete),
+            );
+        }
+
+        recipe_list.into()
+    }
+
+    fn create_parameter_options(&self, index: usize) -> Element<Message> {
+        let selected = &self.selected_operations[index];
+        let operation = TextOperations::get_instance()
+            .get_operation_or_noop(self.operation_ids[selected.operation_index]);
+        let parameters = operation.default_parameters().merged(&selected.parameters);
+
+        let mut parameter_list = widget::column().spacing(2.).padding([0., 0., 0., 10.]);
+
+        for parameter in operation.get_parameters() {
+            let id = parameter.id;
+            let parameter_widget: Element<Message> = match parameter.kind {
+                ParameterKind::Choice { options, .. } => {
+                    let current = parameters.get_text(id);
+                    widget::dropdown(
+                        options,
+                        options.iter().position(|option| *option == current),
+                        move |option_index| {
+                            Message::SetOperationParameter(
+                                index,
+                                id,
+                                ParameterValue::Text(String::from(options[option_index])),
+                            )
+                        },
+                    )
+                    .into()
+                }
+                ParameterKind::Bool { .. } => {
+                    widget::toggler(None, parameters.get_bool(id), move |value| {
+                        Message::SetOperationParameter(index, id, ParameterValue::Bool(value))
+                    })
+                    .into()
+                }
+                ParameterKind::Integer { .. } => {
+                    let text = match &self.editing_integer {
+                        Some((editing_index, editing_id, text))
+                            if *editing_index == index && *editing_id == id =>
+                        {
+                            text.clone()
+                        }
+                        _ => parameters.get_integer(id).to_string(),
+                    };
+                    widget::text_input(parameter.name, text)
+                        .on_input(move |value| Message::IntegerParameterInput(index, id, value))
+                        .on_submit(Message::FinishIntegerParameter)
+                        .into()
+                }
+                ParameterKind::Text { .. } => {
+                    widget::text_input(parameter.name, parameters.get_text(id).to_string())
+                        .on_input(move |value| {
+                            Message::SetOperationParameter(index, id, ParameterValue::Text(value))
+                        })
+                        .into()
+                }
+            };
+
+            parameter_list = parameter_list.push(
+                widget::row()
+                    .spacing(4.)
+                    .align_items(cosmic::iced::Alignment::Center)
+                    .push(widget::text::body(parameter.name).width(Length::Fixed(80.)))
+                    .push(parameter_widget),
+            );
+        }
+
+        parameter_list.into()
+    }
+
     fn create_text_input_view(&self) -> Element<Message> {
         let text_input_copy =
             widget::button::icon(cosmic::widget::icon::from_name("edit-copy-symbolic"))
@@ -348,6 +969,10 @@ impl YourApp {
         let text_input_heading = widget::text::heading("Input Text");
         let text_input_editor = text_editor(&self.content_to_convert.content)
             .font(cosmic::font::FONT_MONO_REGULAR)
+            .highlight::<ErrorHighlighter>(
+                self.input_error_highlight.clone(),
+                error_highlighter::to_format,
+            )
             .on_action(Message::InputContentEditorAction);
 
         widget::column()
@@ -369,16 +994,31 @@ impl YourApp {
                 .tooltip("Copy all")
                 .on_press(Message::CopyConvertedContent);
 
-        let converted_toolbar = widget::row().push(converted_copy).spacing(2);
+        let mut converted_toolbar = widget::row()
+            .spacing(2)
+            .align_items(cosmic::iced::Alignment::Center)
+            .push(converted_copy);
+        if self.converting {
+            converted_toolbar = converted_toolbar.push(widget::text::caption("Converting…"));
+        }
         let converted_heading = cosmic::widget::text::heading("Conversion Result");
         let converted_text_viewer =
             cosmic::iced_widget::text_editor(&self.converted_content.content)
                 .font(cosmic::font::FONT_MONO_REGULAR)
                 .on_action(Message::ConvertedContentEditorAction);
 
-        widget::column()
+        let mut converted_column = widget::column()
             .push(converted_heading)
-            .push(converted_toolbar)
+            .push(converted_toolbar);
+
+        if let Some(error) = &self.conversion_error {
+            converted_column = converted_column.push(
+                widget::text::body(error.as_str())
+                    .style(cosmic::theme::Text::Color(error_highlighter::ERROR_COLOR)),
+            );
+        }
+
+        converted_column
             .push(converted_text_viewer)
             .padding([
                 DEFAULT_PADDING * 2.,
@@ -390,27 +1030,90 @@ impl YourApp {
     }
 }
 
-async fn perform_conversions(source_text: String, conversions: Vec<String>) -> String {
-    let mut source_text = String::from(source_text.trim_end());
+/// Looks for decoding chains on a blocking worker thread, trying them can take a while
+async fn analyse_input(source_text: String) -> Vec<Suggestion> {
+    let source_text = String::from(source_text.trim());
+    if source_text.is_empty() {
+        return vec![];
+    }
+
+    tokio::task::spawn_blocking(move || {
+        magic::suggest_chains(&TextOperationResult::with_string(source_text))
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// Runs the conversion on a blocking worker thread so large inputs do not stall the UI, returning
+/// `None` when the run was cancelled
+async fn perform_conversions(
+    source_text: String,
+    conversions: Vec<PipelineStep>,
+    cancelled: Arc<AtomicBool>,
+) -> Option<ConversionOutcome> {
+    tokio::task::spawn_blocking(move || convert(source_text, conversions, &cancelled))
+        .await
+        .ok()
+        .flatten()
+}
+
+fn convert(
+    source_text: String,
+    conversions: Vec<PipelineStep>,
+    cancelled: &AtomicBool,
+) -> Option<ConversionOutcome> {
+    let source_text = String::from(source_text.trim_end());
 
     if source_text.len() == 0 {
-        String::from("")
+        Some(ConversionOutcome {
+            output: String::from(""),
+            output_is_binary: false,
+            error: None,
+            step_outputs: vec![],
+        })
     } else {
-        let text_operations = TextOperations::get_instance();
+        let input = TextOperationResult::with_string(source_text);
 
-        for operation_name in conversions {
-            let operation = text_operations.get_operation_or_noop(operation_name.as_str());
-            let conversion = operation.convert(source_text.as_str());
+        let (result, step_outputs) =
+            pipeline::run_operations_cancellable(input, &conversions, cancelled)?;
 
-            match conversion {
-                Ok(result) => source_text = result.into(),
-                Err(e) => {
-                    source_text = e.to_string();
-                    break;
-                }
-            };
-        }
+        Some(match result {
+            Ok(result) => ConversionOutcome {
+                output_is_binary: is_binary(&result),
+                output: result.into(),
+                error: None,
+                step_outputs,
+            },
+            Err(e) => {
+                // offsets only refer to the input text when every earlier step left it unchanged
+                let noop_id = TextOperations::get_instance().get_noop().get_id();
+                let input_unchanged = conversions[..e.step]
+                    .iter()
+                    .all(|step| step.operation_id == noop_id);
+                let input_range = match input_unchanged {
+                    true => e.error.get_range(),
+                    false => None,
+                };
 
-        source_text
+                ConversionOutcome {
+                    error: Some(ConversionFailure {
+                        step: e.step,
+                        message: e.to_string(),
+                        input_range,
+                    }),
+                    output_is_binary: is_binary(&e.last_output),
+                    output: e.last_output.into(),
+                    step_outputs,
+                }
+            }
+        })
     }
 }
+
+/// True for bytes that are not UTF-8, which are rendered as hex rather than as text
+fn is_binary(result: &TextOperationResult) -> bool {
+    result
+        .byte_value
+        .as_deref()
+        .is_some_and(|bytes| std::str::from_utf8(bytes).is_err())
+}
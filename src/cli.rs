@@ -0,0 +1,423 @@
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+};
+
+use crate::operations::pipeline::{self, PipelineStep, PipelineStream, StreamError};
+use crate::operations::text_operations::{
+    OperationParameter, OperationParameters, ParameterKind, ParameterValue, TextOperationResult,
+    TextOperations,
+};
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_OPERATION_ERROR: i32 = 1;
+pub const EXIT_USAGE_ERROR: i32 = 2;
+pub const EXIT_IO_ERROR: i32 = 3;
+
+const USAGE: &str = "Usage: text-wrench --cli [OPTIONS] [FILE]
+
+Applies operations to FILE, or stdin when no file is given, and writes the result to stdout.
+
+Options:
+  --op ID              Add an operation to the chain, may be repeated
+  --param NAME=VALUE   Set a parameter on the preceding --op
+  --output FILE        Write the result to FILE instead of stdout
+  --list               List the available operations and their parameters
+  --help               Show this message
+";
+
+/// Arguments have to be UTF-8 as operation ids, parameters and paths are all read as text
+fn decode_arguments(args: &[OsString]) -> Result<Vec<String>, String> {
+    args.iter()
+        .map(|arg| {
+            arg.clone()
+                .into_string()
+                .map_err(|arg| format!("argument is not valid UTF-8: {}", arg.to_string_lossy()))
+        })
+        .collect()
+}
+
+/// What the command line asked for
+#[derive(Debug, PartialEq)]
+enum CliCommand {
+    Help,
+    List,
+    Convert {
+        steps: Vec<PipelineStep>,
+        input: Option<String>,
+        output: Option<String>,
+    },
+}
+
+/// Runs the command line interface with the arguments following `--cli`, returning the exit code
+pub fn run(args: &[OsString]) -> i32 {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let stderr = io::stderr();
+
+    run_with(
+        args,
+        &mut stdin.lock(),
+        &mut stdout.lock(),
+        &mut stderr.lock(),
+    )
+}
+
+fn run_with(
+    args: &[OsString],
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+    errors: &mut dyn Write,
+) -> i32 {
+    let command = match decode_arguments(args).and_then(|args| parse_arguments(&args)) {
+        Ok(command) => command,
+        Err(message) => {
+            let _ = writeln!(errors, "text-wrench: {}\n\n{}", message, USAGE);
+            return EXIT_USAGE_ERROR;
+        }
+    };
+
+    let result = match command {
+        CliCommand::Help => output.write_all(USAGE.as_bytes()),
+        CliCommand::List => list_operations(output),
+        CliCommand::Convert {
+            steps,
+            input: input_path,
+            output: output_path,
+        } => return convert(&steps, input_path, output_path, input, output, errors),
+    };
+
+    match result {
+        Ok(_) => EXIT_SUCCESS,
+        Err(e) => {
+            let _ = writeln!(errors, "text-wrench: {}", e);
+            EXIT_IO_ERROR
+        }
+    }
+}
+
+fn convert(
+    steps: &[PipelineStep],
+    input_path: Option<String>,
+    output_path: Option<String>,
+    input: &mut dyn Read,
+    output: &mut dyn Write,
+    errors: &mut dyn Write,
+) -> i32 {
+    let mut input_file;
+    let input: &mut dyn Read = match &input_path {
+        Some(path) => match File::open(path) {
+            Ok(file) => {
+                input_file = file;
+                &mut input_file
+            }
+            Err(e) => {
+                let _ = writeln!(errors, "text-wrench: {}", e);
+                return EXIT_IO_ERROR;
+            }
+        },
+        None => input,
+    };
+
+    // when every step can stream the input is never held in memory all at once
+    if let Some(stream) = PipelineStream::new(steps) {
+        return convert_stream(stream, input, output_path, output, errors);
+    }
+
+    let mut source: Vec<u8> = vec![];
+    if let Err(e) = input.read_to_end(&mut source) {
+        let _ = writeln!(errors, "text-wrench: {}", e);
+        return EXIT_IO_ERROR;
+    }
+
+    let result = match pipeline::run_operations(TextOperationResult::with_bytes(source), steps) {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = writeln!(errors, "text-wrench: {}", e);
+            return EXIT_OPERATION_ERROR;
+        }
+    };
+
+    let bytes = match (result.byte_value, result.text_value) {
+        (Some(bytes), _) => bytes,
+        (None, Some(text)) => text.into_bytes(),
+        (None, None) => vec![],
+    };
+
+    let written = match &output_path {
+        Some(path) => File::create(path).and_then(|mut file| file.write_all(&bytes)),
+        None => output.write_all(&bytes).and_then(|_| output.flush()),
+    };
+
+    match written {
+        Ok(_) => EXIT_SUCCESS,
+        Err(e) => {
+            let _ = writeln!(errors, "text-wrench: {}", e);
+            EXIT_IO_ERROR
+        }
+    }
+}
+
+fn convert_stream(
+    stream: PipelineStream,
+    input: &mut dyn Read,
+    output_path: Option<String>,
+    output: &mut dyn Write,
+    errors: &mut dyn Write,
+) -> i32 {
+    let mut output_file;
+    let output: &mut dyn Write = match &output_path {
+        Some(path) => match File::create(path) {
+            Ok(file) => {
+                output_file = BufWriter::new(file);
+                &mut output_file
+            }
+            Err(e) => {
+                let _ = writeln!(errors, "text-wrench: {}", e);
+                return EXIT_IO_ERROR;
+            }
+        },
+        None => output,
+    };
+
+    match stream.run(input, output) {
+        Ok(_) => EXIT_SUCCESS,
+        Err(StreamError::Io(e)) => {
+            let _ = writeln!(errors, "text-wrench: {}", e);
+            EXIT_IO_ERROR
+        }
+        Err(StreamError::Step(e)) => {
+            let _ = writeln!(errors, "text-wrench: {}", e);
+            EXIT_OPERATION_ERROR
+        }
+    }
+}
+
+fn list_operations(output: &mut dyn Write) -> io::Result<()> {
+    let text_operations = TextOperations::get_instance();
+    let mut operation_ids = text_operations.get_operations();
+    operation_ids.sort();
+
+    for id in operation_ids {
+        let operation = text_operations.get_operation_or_noop(id);
+        writeln!(output, "{:<20} {}", id, operation.get_name())?;
+
+        for parameter in operation.get_parameters() {
+            writeln!(
+                output,
+                "    {:<16} {}",
+                parameter.id,
+                describe_parameter(parameter)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn describe_parameter(parameter: &OperationParameter) -> String {
+    match parameter.kind {
+        ParameterKind::Choice { options, default } => {
+            format!(
+                "{}: one of {} (default {})",
+                parameter.name,
+                options.join(", "),
+                default
+            )
+        }
+        ParameterKind::Bool { default } => {
+            format!("{}: true or false (default {})", parameter.name, default)
+        }
+        ParameterKind::Integer { min, max, default } => format!(
+            "{}: {} to {} (default {})",
+            parameter.name, min, max, default
+        ),
+        ParameterKind::Text { default } => {
+            format!("{}: text (default \"{}\")", parameter.name, default)
+        }
+    }
+}
+
+fn parse_arguments(args: &[String]) -> Result<CliCommand, String> {
+    let text_operations = TextOperations::get_instance();
+    let mut steps: Vec<PipelineStep> = vec![];
+    let mut input: Option<String> = None;
+    let mut output: Option<String> = None;
+    let mut arguments = args.iter();
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--help" | "-h" => return Ok(CliCommand::Help),
+            "--list" => return Ok(CliCommand::List),
+            "--op" => {
+                let id = arguments.next().ok_or("--op requires an operation id")?;
+                if text_operations.get_operation(id).is_none() {
+                    return Err(format!("unknown operation '{}', see --list", id));
+                }
+                steps.push(PipelineStep::new(id, OperationParameters::default()));
+            }
+            "--param" => {
+                let assignment = arguments.next().ok_or("--param requires NAME=VALUE")?;
+                let step = steps.last_mut().ok_or("--param must follow an --op")?;
+                let (name, value) = assignment
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected NAME=VALUE but found '{}'", assignment))?;
+                let operation = text_operations.get_operation_or_noop(&step.operation_id);
+                let parameter = operation
+                    .get_parameters()
+                    .iter()
+                    .find(|parameter| parameter.id == name)
+                    .ok_or_else(|| {
+                        format!(
+                            "operation '{}' has no parameter '{}'",
+                            step.operation_id, name
+                        )
+                    })?;
+                step.parameters
+                    .set(parameter.id, parse_parameter(parameter, value)?);
+            }
+            "--output" | "-o" => {
+                output = Some(arguments.next().ok_or("--output requires a file")?.clone());
+            }
+            value if value.starts_with('-') && value != "-" => {
+                return Err(format!("unknown option '{}'", value));
+            }
+            value => {
+                if input.is_some() {
+                    return Err(String::from("only one input file can be given"));
+                }
+                if value != "-" {
+                    input = Some(String::from(value));
+                }
+            }
+        }
+    }
+
+    Ok(CliCommand::Convert {
+        steps,
+        input,
+        output,
+    })
+}
+
+fn parse_parameter(parameter: &OperationParameter, value: &str) -> Result<ParameterValue, String> {
+    let invalid = || {
+        format!(
+            "invalid value '{}' for parameter '{}', expected {}",
+            value,
+            parameter.id,
+            describe_parameter(parameter)
+        )
+    };
+
+    match parameter.kind {
+        ParameterKind::Choice { options, .. } => options
+            .iter()
+            .find(|option| option.eq_ignore_ascii_case(value))
+            .map(|option| ParameterValue::Text(String::from(*option)))
+            .ok_or_else(invalid),
+        ParameterKind::Bool { .. } => match value {
+            "true" | "yes" | "1" => Ok(ParameterValue::Bool(true)),
+            "false" | "no" | "0" => Ok(ParameterValue::Bool(false)),
+            _ => Err(invalid()),
+        },
+        ParameterKind::Integer { min, max, .. } => match value.parse::<i64>() {
+            Ok(number) if number >= min && number <= max => Ok(ParameterValue::Integer(number)),
+            _ => Err(invalid()),
+        },
+        ParameterKind::Text { .. } => Ok(ParameterValue::Text(String::from(value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_cli(args: &[&str], input: &[u8]) -> (i32, Vec<u8>, String) {
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        let mut output: Vec<u8> = vec![];
+        let mut errors: Vec<u8> = vec![];
+
+        let code = run_with(&args, &mut &input[..], &mut output, &mut errors);
+        (code, output, String::from_utf8(errors).unwrap())
+    }
+
+    #[test]
+    fn it_applies_operations_in_order() {
+        let (code, output, _) = run_cli(&["--op", "TO_HEX", "--op", "TO_BASE64"], b"AB");
+
+        assert_eq!(code, EXIT_SUCCESS);
+        assert_eq!(output, b"NDE0Mg==");
+    }
+
+    #[test]
+    fn it_passes_binary_data_through_untouched() {
+        let (code, output, _) = run_cli(&["--op", "TO_BASE64", "--op", "FROM_BASE64"], b"\xff\x00");
+
+        assert_eq!(code, EXIT_SUCCESS);
+        assert_eq!(output, b"\xff\x00");
+    }
+
+    #[test]
+    fn it_applies_parameters_to_the_preceding_operation() {
+        let (code, output, _) = run_cli(&["--op", "TO_HEX", "--param", "separator=:"], b"AB");
+
+        assert_eq!(code, EXIT_SUCCESS);
+        assert_eq!(output, b"41:42");
+    }
+
+    #[test]
+    fn it_fails_when_an_operation_fails() {
+        let (code, output, errors) = run_cli(&["--op", "FROM_HEX"], b"4g");
+
+        assert_eq!(code, EXIT_OPERATION_ERROR);
+        assert!(output.is_empty());
+        assert!(!errors.is_empty());
+    }
+
+    #[test]
+    fn it_rejects_unknown_operations_and_parameters() {
+        assert_eq!(
+            run_cli(&["--op", "NOT_AN_OPERATION"], b"").0,
+            EXIT_USAGE_ERROR
+        );
+        assert_eq!(
+            run_cli(&["--op", "TO_HEX", "--param", "nope=1"], b"").0,
+            EXIT_USAGE_ERROR
+        );
+        assert_eq!(
+            run_cli(&["--op", "TO_BASE64", "--param", "padding=maybe"], b"").0,
+            EXIT_USAGE_ERROR
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_rejects_arguments_that_are_not_utf8() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let args = [
+            OsString::from("--op"),
+            OsString::from("TO_HEX"),
+            OsString::from("--output"),
+            OsString::from_vec(b"out\xff.txt".to_vec()),
+        ];
+        let mut output: Vec<u8> = vec![];
+        let mut errors: Vec<u8> = vec![];
+
+        let code = run_with(&args, &mut &b"AB"[..], &mut output, &mut errors);
+        assert_eq!(code, EXIT_USAGE_ERROR);
+        assert!(String::from_utf8(errors).unwrap().contains("Usage:"));
+    }
+
+    #[test]
+    fn it_lists_operations() {
+        let (code, output, _) = run_cli(&["--list"], b"");
+        let listing = String::from_utf8(output).unwrap();
+
+        assert_eq!(code, EXIT_SUCCESS);
+        assert!(listing.contains("TO_BASE64"));
+        assert!(listing.contains("padding"));
+    }
+}
@@ -0,0 +1,94 @@
+use std::ops::Range;
+
+use cosmic::iced::{Color, Font};
+use cosmic::iced_core::text::highlighter::{Format, Highlighter};
+
+pub const ERROR_COLOR: Color = Color::from_rgb(0.88, 0.11, 0.14);
+
+/// The part of each editor line that should be marked as invalid
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorHighlightSettings {
+    lines: Vec<(usize, Range<usize>)>,
+}
+
+impl ErrorHighlightSettings {
+    /// Splits a byte range of `text` into the ranges it covers on each line
+    pub fn from_range(text: &str, range: Range<usize>) -> Self {
+        let mut end = range.end.min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let mut lines = vec![];
+        let mut line_start = 0;
+        for (line_number, line) in text.split('\n').enumerate() {
+            let line_end = line_start + line.len();
+            let start = range.start.max(line_start);
+            if start < end.min(line_end) {
+                lines.push((
+                    line_number,
+                    start - line_start..end.min(line_end) - line_start,
+                ));
+            }
+
+            line_start = line_end + 1;
+            if line_start >= end {
+                break;
+            }
+        }
+
+        ErrorHighlightSettings { lines }
+    }
+}
+
+/// Highlights the range of the input that an operation reported as invalid
+pub struct ErrorHighlighter {
+    settings: ErrorHighlightSettings,
+    current_line: usize,
+}
+
+impl Highlighter for ErrorHighlighter {
+    type Settings = ErrorHighlightSettings;
+    type Highlight = ();
+    type Iterator<'a> = std::vec::IntoIter<(Range<usize>, ())>;
+
+    fn new(settings: &Self::Settings) -> Self {
+        ErrorHighlighter {
+            settings: settings.clone(),
+            current_line: 0,
+        }
+    }
+
+    fn update(&mut self, new_settings: &Self::Settings) {
+        self.settings = new_settings.clone();
+        self.current_line = 0;
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = line;
+    }
+
+    fn highlight_line(&mut self, _line: &str) -> Self::Iterator<'_> {
+        let highlights: Vec<(Range<usize>, ())> = self
+            .settings
+            .lines
+            .iter()
+            .filter(|(line_number, _)| *line_number == self.current_line)
+            .map(|(_, range)| (range.clone(), ()))
+            .collect();
+
+        self.current_line += 1;
+        highlights.into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+pub fn to_format(_highlight: &(), _theme: &cosmic::Theme) -> Format<Font> {
+    Format {
+        color: Some(ERROR_COLOR),
+        font: None,
+    }
+}
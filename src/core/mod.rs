@@ -1 +1,3 @@
+pub mod error_highlighter;
 pub mod localization;
+pub mod recipes;
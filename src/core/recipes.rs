@@ -0,0 +1,235 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fl;
+use crate::operations::pipeline::PipelineStep;
+use crate::operations::text_operations::TextOperations;
+
+/// The version of the recipe file format written by this build
+pub const RECIPES_VERSION: u32 = 1;
+
+const RECIPES_DIRECTORY: &str = "com.mangledbits.TextWrench";
+const RECIPES_FILE: &str = "recipes.toml";
+const RECIPES_BACKUP_EXTENSION: &str = "toml.bak";
+
+#[derive(Debug)]
+pub enum RecipeError {
+    Io(std::io::Error),
+    Parse(String),
+    NewerVersion,
+}
+
+impl Display for RecipeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            RecipeError::Io(e) => fl!("recipe-error-io", message = e.to_string()),
+            RecipeError::Parse(message) => {
+                fl!("recipe-error-parse", message = message.as_str())
+            }
+            RecipeError::NewerVersion => fl!("recipe-error-newer-version"),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for RecipeError {}
+
+/// A named chain of operations and the parameters for each of them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recipe {
+    pub name: String,
+    #[serde(default)]
+    pub steps: Vec<PipelineStep>,
+}
+
+impl Recipe {
+    /// Splits the steps into those that can be run and the ids of operations that are not
+    /// registered, for example ones saved by a newer version
+    pub fn resolve_steps(&self) -> (Vec<PipelineStep>, Vec<String>) {
+        let text_operations = TextOperations::get_instance();
+        let mut steps = vec![];
+        let mut unknown_ids = vec![];
+
+        for step in &self.steps {
+            match text_operations.get_operation(step.operation_id.as_str()) {
+                Some(_) => steps.push(step.clone()),
+                None => unknown_ids.push(step.operation_id.clone()),
+            }
+        }
+
+        (steps, unknown_ids)
+    }
+}
+
+/// The collection of recipes stored in the user's config directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecipeBook {
+    pub version: u32,
+    #[serde(default)]
+    pub recipes: Vec<Recipe>,
+}
+
+impl Default for RecipeBook {
+    fn default() -> Self {
+        RecipeBook {
+            version: RECIPES_VERSION,
+            recipes: vec![],
+        }
+    }
+}
+
+impl RecipeBook {
+    /// The location of the recipe file in the XDG config directory
+    pub fn get_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(RECIPES_DIRECTORY).join(RECIPES_FILE))
+    }
+
+    /// Loads the recipe file, a missing file is treated as an empty book
+    pub fn load() -> Result<RecipeBook, RecipeError> {
+        match RecipeBook::get_path() {
+            Some(path) if path.exists() => RecipeBook::load_from(&path),
+            _ => Ok(RecipeBook::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<RecipeBook, RecipeError> {
+        let contents = fs::read_to_string(path).map_err(RecipeError::Io)?;
+        RecipeBook::from_toml(contents.as_str())
+    }
+
+    pub fn save(&self) -> Result<(), RecipeError> {
+        match RecipeBook::get_path() {
+            Some(path) => self.save_to(&path),
+            None => Err(RecipeError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "no config directory",
+            ))),
+        }
+    }
+
+    /// Writes the book to `path`, an existing file that cannot be read is first copied next
+    /// to it with a `.bak` extension so it is not lost
+    pub fn save_to(&self, path: &Path) -> Result<(), RecipeError> {
+        let contents = self.to_toml()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(RecipeError::Io)?;
+        }
+        if path.exists() && RecipeBook::load_from(path).is_err() {
+            fs::copy(path, path.with_extension(RECIPES_BACKUP_EXTENSION))
+                .map_err(RecipeError::Io)?;
+        }
+        fs::write(path, contents).map_err(RecipeError::Io)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<RecipeBook, RecipeError> {
+        toml::from_str(contents).map_err(|e| RecipeError::Parse(e.to_string()))
+    }
+
+    /// Fails for a book loaded from a newer version, writing it would drop what this build
+    /// does not understand
+    pub fn to_toml(&self) -> Result<String, RecipeError> {
+        if self.is_newer_version() {
+            return Err(RecipeError::NewerVersion);
+        }
+        let book = RecipeBook {
+            version: RECIPES_VERSION,
+            recipes: self.recipes.clone(),
+        };
+        toml::to_string_pretty(&book).map_err(|e| RecipeError::Parse(e.to_string()))
+    }
+
+    /// True when the file was written by a newer version that may use features this build
+    /// does not understand
+    pub fn is_newer_version(&self) -> bool {
+        self.version > RECIPES_VERSION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operations::{OperationParameters, ParameterValue};
+
+    #[test]
+    fn it_round_trips_recipes_through_toml() {
+        let parameters = OperationParameters::default()
+            .with("separator", ParameterValue::Text(String::from(":")))
+            .with("padding", ParameterValue::Bool(false));
+        let book = RecipeBook {
+            version: RECIPES_VERSION,
+            recipes: vec![Recipe {
+                name: String::from("Hex then Base64"),
+                steps: vec![
+                    PipelineStep::new("TO_HEX", parameters),
+                    PipelineStep::new("TO_BASE64", OperationParameters::default()),
+                ],
+            }],
+        };
+
+        let loaded = RecipeBook::from_toml(book.to_toml().unwrap().as_str()).unwrap();
+        assert_eq!(loaded, book);
+    }
+
+    #[test]
+    fn it_skips_unknown_operations_when_resolving() {
+        let book = RecipeBook::from_toml(
+            r#"
+            version = 2
+
+            [[recipes]]
+            name = "From the future"
+
+            [[recipes.steps]]
+            operation_id = "TO_HEX"
+
+            [[recipes.steps]]
+            operation_id = "SOME_NEW_OPERATION"
+            "#,
+        )
+        .unwrap();
+
+        let (steps, unknown_ids) = book.recipes[0].resolve_steps();
+        assert!(book.is_newer_version());
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].operation_id, "TO_HEX");
+        assert_eq!(unknown_ids, vec![String::from("SOME_NEW_OPERATION")]);
+    }
+
+    #[test]
+    fn it_reports_invalid_recipe_files() {
+        let book = RecipeBook::from_toml("version = \"one\"");
+        assert!(matches!(book, Err(RecipeError::Parse(_))));
+    }
+
+    #[test]
+    fn it_does_not_save_books_from_a_newer_version() {
+        let book = RecipeBook {
+            version: RECIPES_VERSION + 1,
+            recipes: vec![],
+        };
+
+        assert!(matches!(book.to_toml(), Err(RecipeError::NewerVersion)));
+    }
+
+    #[test]
+    fn it_backs_up_unreadable_files_before_saving() {
+        let directory = std::env::temp_dir().join(format!("text-wrench-{}", std::process::id()));
+        let path = directory.join(RECIPES_FILE);
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(&path, "version = \"one\"").unwrap();
+
+        RecipeBook::default().save_to(&path).unwrap();
+        let backup = fs::read_to_string(path.with_extension(RECIPES_BACKUP_EXTENSION));
+        let saved = RecipeBook::load_from(&path);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(backup.unwrap(), "version = \"one\"");
+        assert_eq!(saved.unwrap(), RecipeBook::default());
+    }
+}
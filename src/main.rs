@@ -2,6 +2,7 @@ use app::YourApp;
 use cosmic::iced::{Limits, Size};
 /// The `app` module is used by convention to indicate the main component of our application.
 mod app;
+mod cli;
 mod core;
 mod operations;
 
@@ -11,6 +12,12 @@ mod operations;
 /// - `()` is the flags that your app needs to use before it starts.
 ///  If your app does not need any flags, you can pass in `()`.
 fn main() -> cosmic::iced::Result {
+    // `--cli` runs the operations headless on stdin or a file instead of starting the GUI
+    let args: Vec<std::ffi::OsString> = std::env::args_os().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "--cli") {
+        std::process::exit(cli::run(&args[1..]));
+    }
+
     let settings = cosmic::app::Settings::default()
         .size_limits(Limits::new(Size::new(600., 400.), Size::INFINITY));
     //.theme(cosmic::Theme::light())
@@ -0,0 +1,662 @@
+use super::text_operation::{
+    OperationParameter, OperationParameters, ParameterKind, TextOperation, TextOperationError,
+    TextOperationResult,
+};
+
+const MORSE_PARAMETERS: &[OperationParameter] = &[
+    OperationParameter {
+        id: "dot",
+        name: "Dot",
+        kind: ParameterKind::Text { default: "." },
+    },
+    OperationParameter {
+        id: "dash",
+        name: "Dash",
+        kind: ParameterKind::Text { default: "-" },
+    },
+    OperationParameter {
+        id: "letter_separator",
+        name: "Letter separator",
+        kind: ParameterKind::Text { default: " " },
+    },
+    OperationParameter {
+        id: "word_separator",
+        name: "Word separator",
+        kind: ParameterKind::Text { default: " / " },
+    },
+];
+
+/// International Morse code, dots and dashes are replaced by the symbols in the parameters
+const MORSE_CODE: &[(char, &str)] = &[
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+    ('.', ".-.-.-"),
+    (',', "--..--"),
+    ('?', "..--.."),
+    ('\'', ".----."),
+    ('!', "-.-.--"),
+    ('/', "-..-."),
+    ('(', "-.--."),
+    (')', "-.--.-"),
+    ('&', ".-..."),
+    (':', "---..."),
+    (';', "-.-.-."),
+    ('=', "-...-"),
+    ('+', ".-.-."),
+    ('-', "-....-"),
+    ('_', "..--.-"),
+    ('"', ".-..-."),
+    ('$', "...-..-"),
+    ('@', ".--.-."),
+];
+
+/// The NATO spelling of letters and digits, and names for the symbols that show up in keys
+const NATO_ALPHABET: &[(char, &str)] = &[
+    ('a', "Alfa"),
+    ('b', "Bravo"),
+    ('c', "Charlie"),
+    ('d', "Delta"),
+    ('e', "Echo"),
+    ('f', "Foxtrot"),
+    ('g', "Golf"),
+    ('h', "Hotel"),
+    ('i', "India"),
+    ('j', "Juliett"),
+    ('k', "Kilo"),
+    ('l', "Lima"),
+    ('m', "Mike"),
+    ('n', "November"),
+    ('o', "Oscar"),
+    ('p', "Papa"),
+    ('q', "Quebec"),
+    ('r', "Romeo"),
+    ('s', "Sierra"),
+    ('t', "Tango"),
+    ('u', "Uniform"),
+    ('v', "Victor"),
+    ('w', "Whiskey"),
+    ('x', "X-ray"),
+    ('y', "Yankee"),
+    ('z', "Zulu"),
+    ('0', "Zero"),
+    ('1', "One"),
+    ('2', "Two"),
+    ('3', "Three"),
+    ('4', "Four"),
+    ('5', "Five"),
+    ('6', "Six"),
+    ('7', "Seven"),
+    ('8', "Eight"),
+    ('9', "Nine"),
+    (' ', "Space"),
+    ('.', "Dot"),
+    (',', "Comma"),
+    ('-', "Dash"),
+    ('_', "Underscore"),
+    ('/', "Slash"),
+    ('\\', "Backslash"),
+    ('@', "At"),
+    ('#', "Hash"),
+    ('!', "Exclamation"),
+    ('?', "Question"),
+    ('$', "Dollar"),
+    ('%', "Percent"),
+    ('&', "Ampersand"),
+    ('*', "Asterisk"),
+    ('+', "Plus"),
+    ('=', "Equals"),
+    (':', "Colon"),
+    (';', "Semicolon"),
+];
+
+/// Other spellings that are read back to the same character
+const NATO_ALTERNATIVES: &[(char, &str)] = &[('a', "Alpha"), ('j', "Juliet"), ('x', "Xray")];
+
+/// Letters in English Braille, the first ten are also the digits after a number sign
+const BRAILLE_LETTERS: [char; 26] = [
+    '⠁', '⠃', '⠉', '⠙', '⠑', '⠋', '⠛', '⠓', '⠊', '⠚', '⠅', '⠇', '⠍', '⠝', '⠕', '⠏', '⠟', '⠗', '⠎',
+    '⠞', '⠥', '⠧', '⠺', '⠭', '⠽', '⠵',
+];
+
+const BRAILLE_PUNCTUATION: &[(char, char)] = &[
+    (',', '⠂'),
+    (';', '⠆'),
+    (':', '⠒'),
+    ('.', '⠲'),
+    ('!', '⠖'),
+    ('?', '⠦'),
+    ('\'', '⠄'),
+    ('-', '⠤'),
+];
+
+const BRAILLE_CAPITAL: char = '⠠';
+const BRAILLE_NUMBER: char = '⠼';
+/// Ends a number so the letters a to j that follow are not read as digits
+const BRAILLE_LETTER: char = '⠰';
+const BRAILLE_BLANK: char = '\u{2800}';
+
+/// Splits the input into lines, keeping the line ending apart so it can be copied as is
+fn lines_with_offsets(input: &str) -> impl Iterator<Item = (usize, &str, &str)> {
+    input.split_inclusive('\n').scan(0, |start, line| {
+        let offset = *start;
+        *start += line.len();
+        let content = line.trim_end_matches(['\r', '\n']);
+        Some((offset, content, &line[content.len()..]))
+    })
+}
+
+pub struct OperationToMorse {}
+
+impl TextOperation for OperationToMorse {
+    fn get_id(&self) -> &'static str {
+        "TO_MORSE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Text to Morse Code"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_MORSE"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        MORSE_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let dot = parameters.get_text("dot");
+        let dash = parameters.get_text("dash");
+        let letter_separator = parameters.get_text("letter_separator");
+        let word_separator = parameters.get_text("word_separator");
+
+        let mut encoded = String::with_capacity(input.len() * 4);
+        for (offset, line, ending) in lines_with_offsets(input) {
+            let mut words = vec![];
+            let mut word_start = offset;
+            for word in line.split(' ') {
+                let mut letters = vec![];
+                for (index, c) in word.char_indices() {
+                    let code = MORSE_CODE
+                        .iter()
+                        .find(|(letter, _)| *letter == c.to_ascii_uppercase())
+                        .ok_or(TextOperationError::InvalidInputAtOffset(word_start + index))?
+                        .1;
+                    let symbols: Vec<&str> = code
+                        .chars()
+                        .map(|symbol| if symbol == '.' { dot } else { dash })
+                        .collect();
+                    letters.push(symbols.concat());
+                }
+                words.push(letters.join(letter_separator));
+                word_start += word.len() + 1;
+            }
+            encoded.push_str(&words.join(word_separator));
+            encoded.push_str(ending);
+        }
+
+        Ok(TextOperationResult::with_string(encoded))
+    }
+}
+
+pub struct OperationFromMorse {}
+
+impl TextOperation for OperationFromMorse {
+    fn get_id(&self) -> &'static str {
+        "FROM_MORSE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Morse Code to Text"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_MORSE"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        MORSE_PARAMETERS
+    }
+
+    /// Reads the letters in upper case, spaces and tabs that are not part of a separator are
+    /// ignored
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let dot = parameters.get_text("dot");
+        let dash = parameters.get_text("dash");
+        let letter_separator = parameters.get_text("letter_separator");
+        let word_separator = parameters.get_text("word_separator");
+        // a word separator is still recognized if the spaces around it were lost
+        let word_separators = [word_separator, word_separator.trim()];
+
+        let mut decoded = String::with_capacity(input.len() / 4);
+        for (offset, line, ending) in lines_with_offsets(input) {
+            let mut position = 0;
+            while position < line.len() {
+                let rest = &line[position..];
+                if let Some(separator) = word_separators
+                    .iter()
+                    .find(|separator| !separator.is_empty() && rest.starts_with(**separator))
+                {
+                    decoded.push(' ');
+                    position += separator.len();
+                    continue;
+                }
+                if !letter_separator.is_empty() && rest.starts_with(letter_separator) {
+                    position += letter_separator.len();
+                    continue;
+                }
+                if rest.starts_with([' ', '\t']) {
+                    position += 1;
+                    continue;
+                }
+
+                let start = position;
+                let mut code = String::new();
+                loop {
+                    let rest = &line[position..];
+                    if !dot.is_empty() && rest.starts_with(dot) {
+                        code.push('.');
+                        position += dot.len();
+                    } else if !dash.is_empty() && rest.starts_with(dash) {
+                        code.push('-');
+                        position += dash.len();
+                    } else {
+                        break;
+                    }
+                }
+
+                let letter = MORSE_CODE
+                    .iter()
+                    .find(|(_, letter_code)| !code.is_empty() && *letter_code == code)
+                    .ok_or(TextOperationError::InvalidInputAtOffset(offset + start))?;
+                decoded.push(letter.0);
+            }
+            decoded.push_str(ending);
+        }
+
+        Ok(TextOperationResult::with_string(decoded))
+    }
+}
+
+pub struct OperationToNato {}
+
+impl TextOperation for OperationToNato {
+    fn get_id(&self) -> &'static str {
+        "TO_NATO"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Text to NATO Phonetic"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_NATO"))
+    }
+
+    /// Spells out every character, capital letters are written in upper case so the case of
+    /// a key can be read out
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut spelled = String::with_capacity(input.len() * 6);
+        for (_, line, ending) in lines_with_offsets(input) {
+            let words: Vec<String> = line
+                .chars()
+                .map(|c| {
+                    match NATO_ALPHABET
+                        .iter()
+                        .find(|(letter, _)| *letter == c.to_ascii_lowercase())
+                    {
+                        Some((_, word)) if c.is_ascii_uppercase() => word.to_uppercase(),
+                        Some((_, word)) => word.to_string(),
+                        // anything without a name is kept, on its own
+                        None => c.to_string(),
+                    }
+                })
+                .collect();
+            spelled.push_str(&words.join(" "));
+            spelled.push_str(ending);
+        }
+
+        Ok(TextOperationResult::with_string(spelled))
+    }
+}
+
+pub struct OperationFromNato {}
+
+impl TextOperation for OperationFromNato {
+    fn get_id(&self) -> &'static str {
+        "FROM_NATO"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "NATO Phonetic to Text"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_NATO"))
+    }
+
+    /// Reads words written in all capitals as upper case letters, any other case as lower case
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut decoded = String::with_capacity(input.len() / 4);
+        for (offset, line, ending) in lines_with_offsets(input) {
+            let mut word_start = 0;
+            for word in line.split([' ', '\t']) {
+                let start = word_start;
+                word_start += word.len() + 1;
+                if word.is_empty() {
+                    continue;
+                }
+
+                let c = NATO_ALPHABET
+                    .iter()
+                    .chain(NATO_ALTERNATIVES)
+                    .find(|(_, spelling)| spelling.eq_ignore_ascii_case(word))
+                    .map(|(c, _)| *c)
+                    .or_else(|| {
+                        let mut chars = word.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) if !c.is_alphanumeric() => Some(c),
+                            _ => None,
+                        }
+                    })
+                    .ok_or(TextOperationError::InvalidInputAtOffset(offset + start))?;
+
+                let capital = word.len() > 1 && !word.bytes().any(|b| b.is_ascii_lowercase());
+                match capital {
+                    true => decoded.push(c.to_ascii_uppercase()),
+                    false => decoded.push(c),
+                }
+            }
+            decoded.push_str(ending);
+        }
+
+        Ok(TextOperationResult::with_string(decoded))
+    }
+}
+
+pub struct OperationToBraille {}
+
+impl TextOperation for OperationToBraille {
+    fn get_id(&self) -> &'static str {
+        "TO_BRAILLE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Text to Braille"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_BRAILLE"))
+    }
+
+    /// Writes uncontracted English Braille with Unicode Braille patterns
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut braille = String::with_capacity(input.len() * 3);
+        let mut number = false;
+
+        for (index, c) in input.char_indices() {
+            match c {
+                'a'..='z' | 'A'..='Z' => {
+                    let letter = c.to_ascii_lowercase();
+                    if c.is_ascii_uppercase() {
+                        braille.push(BRAILLE_CAPITAL);
+                    } else if number && letter <= 'j' {
+                        braille.push(BRAILLE_LETTER);
+                    }
+                    braille.push(BRAILLE_LETTERS[(letter as u8 - b'a') as usize]);
+                    number = false;
+                }
+                '0'..='9' => {
+                    if !number {
+                        braille.push(BRAILLE_NUMBER);
+                        number = true;
+                    }
+                    // zero is written like j, the tenth letter
+                    let digit = (c as u8 - b'0' + 9) % 10;
+                    braille.push(BRAILLE_LETTERS[digit as usize]);
+                }
+                ' ' | '\t' | '\r' | '\n' => {
+                    braille.push(c);
+                    number = false;
+                }
+                _ => {
+                    let cell = BRAILLE_PUNCTUATION
+                        .iter()
+                        .find(|(punctuation, _)| *punctuation == c)
+                        .ok_or(TextOperationError::InvalidInputAtOffset(index))?;
+                    braille.push(cell.1);
+                    // a decimal point does not end a number
+                    number = number && c == '.';
+                }
+            }
+        }
+
+        Ok(TextOperationResult::with_string(braille))
+    }
+}
+
+pub struct OperationFromBraille {}
+
+impl TextOperation for OperationFromBraille {
+    fn get_id(&self) -> &'static str {
+        "FROM_BRAILLE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Braille to Text"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_BRAILLE"))
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut text = String::with_capacity(input.len() / 3);
+        let mut number = false;
+        let mut capital = false;
+
+        for (index, cell) in input.char_indices() {
+            if let Some(letter) = BRAILLE_LETTERS.iter().position(|letter| *letter == cell) {
+                if number && letter < 10 {
+                    text.push((b'0' + (letter as u8 + 1) % 10) as char);
+                } else {
+                    let letter = (b'a' + letter as u8) as char;
+                    match capital {
+                        true => text.push(letter.to_ascii_uppercase()),
+                        false => text.push(letter),
+                    }
+                    number = false;
+                }
+                capital = false;
+                continue;
+            }
+
+            if capital {
+                // a capital sign has to be followed by a letter
+                return Err(TextOperationError::InvalidInputAtOffset(
+                    index - BRAILLE_CAPITAL.len_utf8(),
+                ));
+            }
+            match cell {
+                BRAILLE_CAPITAL => capital = true,
+                BRAILLE_NUMBER => number = true,
+                BRAILLE_LETTER => number = false,
+                BRAILLE_BLANK | ' ' => {
+                    text.push(' ');
+                    number = false;
+                }
+                '\t' | '\r' | '\n' => {
+                    text.push(cell);
+                    number = false;
+                }
+                _ => {
+                    let punctuation = BRAILLE_PUNCTUATION
+                        .iter()
+                        .find(|(_, punctuation)| *punctuation == cell)
+                        .ok_or(TextOperationError::InvalidInputAtOffset(index))?;
+                    text.push(punctuation.0);
+                    number = number && punctuation.0 == '.';
+                }
+            }
+        }
+
+        Ok(TextOperationResult::with_string(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    #[test]
+    fn it_converts_text_to_morse_and_back() {
+        let to_morse = OperationToMorse {};
+        let from_morse = OperationFromMorse {};
+
+        let result = to_morse.convert("SOS, help\nme", &to_morse.default_parameters());
+        let morse = result.unwrap().text_value.unwrap();
+        assert_eq!(morse, "... --- ... --..-- / .... . .-.. .--.\n-- .");
+
+        let result = from_morse.convert(&morse, &from_morse.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "SOS, HELP\nME");
+    }
+
+    #[test]
+    fn it_uses_custom_morse_symbols() {
+        let to_morse = OperationToMorse {};
+        let from_morse = OperationFromMorse {};
+        let parameters = to_morse
+            .default_parameters()
+            .with("dot", ParameterValue::Text(String::from("di")))
+            .with("dash", ParameterValue::Text(String::from("dah")))
+            .with("letter_separator", ParameterValue::Text(String::from(" ")))
+            .with("word_separator", ParameterValue::Text(String::from(" | ")));
+
+        let result = to_morse.convert("ok go", &parameters);
+        let morse = result.unwrap().text_value.unwrap();
+        assert_eq!(morse, "dahdahdah dahdidah | dahdahdi dahdahdah");
+
+        let result = from_morse.convert(&morse, &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "OK GO");
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_unknown_morse_symbols() {
+        let to_morse = OperationToMorse {};
+        let from_morse = OperationFromMorse {};
+
+        let result = to_morse.convert("ab~", &to_morse.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(2..3));
+
+        let result = from_morse.convert("... x ...", &from_morse.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(4..5));
+
+        let result = from_morse.convert("... -------", &from_morse.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(4..5));
+    }
+
+    #[test]
+    fn it_spells_keys_with_the_nato_alphabet_and_back() {
+        let to_nato = OperationToNato {};
+        let from_nato = OperationFromNato {};
+
+        let result = to_nato.convert("aB3-x", &to_nato.default_parameters());
+        let spelled = result.unwrap().text_value.unwrap();
+        assert_eq!(spelled, "Alfa BRAVO Three Dash X-ray");
+
+        let result = from_nato.convert(&spelled, &from_nato.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "aB3-x");
+
+        let result = from_nato.convert("alpha JULIET ~", &from_nato.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "aJ~");
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_unknown_nato_words() {
+        let operation = OperationFromNato {};
+
+        let result = operation.convert("Alfa\nBravo Chalie", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(11..12));
+    }
+
+    #[test]
+    fn it_converts_text_to_braille_and_back() {
+        let to_braille = OperationToBraille {};
+        let from_braille = OperationFromBraille {};
+
+        let result = to_braille.convert("Hello, room 101a.", &to_braille.default_parameters());
+        let braille = result.unwrap().text_value.unwrap();
+        assert_eq!(braille, "⠠⠓⠑⠇⠇⠕⠂ ⠗⠕⠕⠍ ⠼⠁⠚⠁⠰⠁⠲");
+
+        let result = from_braille.convert(&braille, &from_braille.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "Hello, room 101a.");
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_unknown_braille_cells() {
+        let operation = OperationFromBraille {};
+
+        // each cell is three bytes in UTF-8
+        let result = operation.convert("⠁⠃⣿", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(6..7));
+    }
+}
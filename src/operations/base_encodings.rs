@@ -0,0 +1,812 @@
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, TextOperation,
+    TextOperationError, TextOperationResult,
+};
+
+const BASE32_ALPHABET_RFC4648: &str = "RFC 4648";
+const BASE32_ALPHABET_HEX: &str = "Base32hex";
+const BASE32_ALPHABET_CROCKFORD: &str = "Crockford";
+
+const BASE58_ALPHABET_BITCOIN: &str = "Bitcoin";
+const BASE58_ALPHABET_FLICKR: &str = "Flickr";
+const BASE58_ALPHABET_RIPPLE: &str = "Ripple";
+
+const BASE85_VARIANT_ASCII85: &str = "Ascii85";
+const BASE85_VARIANT_Z85: &str = "Z85";
+
+const BASE32_ALPHABET_PARAMETER: OperationParameter = OperationParameter {
+    id: "alphabet",
+    name: "Alphabet",
+    kind: ParameterKind::Choice {
+        options: &[
+            BASE32_ALPHABET_RFC4648,
+            BASE32_ALPHABET_HEX,
+            BASE32_ALPHABET_CROCKFORD,
+        ],
+        default: BASE32_ALPHABET_RFC4648,
+    },
+};
+
+const ENCODE_BASE32_PARAMETERS: &[OperationParameter] = &[
+    BASE32_ALPHABET_PARAMETER,
+    OperationParameter {
+        id: "padding",
+        name: "Padding",
+        kind: ParameterKind::Bool { default: true },
+    },
+];
+
+const DECODE_BASE32_PARAMETERS: &[OperationParameter] = &[BASE32_ALPHABET_PARAMETER];
+
+const BASE58_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "alphabet",
+    name: "Alphabet",
+    kind: ParameterKind::Choice {
+        options: &[
+            BASE58_ALPHABET_BITCOIN,
+            BASE58_ALPHABET_FLICKR,
+            BASE58_ALPHABET_RIPPLE,
+        ],
+        default: BASE58_ALPHABET_BITCOIN,
+    },
+}];
+
+const BASE85_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "variant",
+    name: "Variant",
+    kind: ParameterKind::Choice {
+        options: &[BASE85_VARIANT_ASCII85, BASE85_VARIANT_Z85],
+        default: BASE85_VARIANT_ASCII85,
+    },
+}];
+
+fn base32_alphabet(name: &str) -> &'static [u8; 32] {
+    match name {
+        BASE32_ALPHABET_HEX => b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+        BASE32_ALPHABET_CROCKFORD => b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+        _ => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+    }
+}
+
+fn base58_alphabet(name: &str) -> &'static [u8; 58] {
+    match name {
+        BASE58_ALPHABET_FLICKR => b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ",
+        BASE58_ALPHABET_RIPPLE => b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz",
+        _ => b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    }
+}
+
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+const BASE45_ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Looks up the value of a symbol, `None` when it is not part of the alphabet
+fn symbol_value(alphabet: &[u8], symbol: char) -> Option<u32> {
+    match symbol.is_ascii() {
+        true => alphabet
+            .iter()
+            .position(|a| *a == symbol as u8)
+            .map(|value| value as u32),
+        false => None,
+    }
+}
+
+fn into_string(encoded: Vec<u8>) -> Result<TextOperationResult, TextOperationError> {
+    String::from_utf8(encoded)
+        .map(TextOperationResult::with_string)
+        .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))
+}
+
+pub struct OperationEncodeBase32 {}
+
+impl TextOperation for OperationEncodeBase32 {
+    fn get_id(&self) -> &'static str {
+        "TO_BASE32"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base32 Encode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_BASE32"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        ENCODE_BASE32_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let alphabet_name = parameters.get_text("alphabet");
+        let alphabet = base32_alphabet(alphabet_name);
+        let mut encoded: Vec<u8> = Vec::with_capacity(input.len().div_ceil(5) * 8);
+        let mut buffer: u32 = 0;
+        let mut bits: u32 = 0;
+
+        for b in input {
+            buffer = (buffer << 8) | *b as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                encoded.push(alphabet[((buffer >> bits) & 0x1f) as usize]);
+            }
+        }
+        if bits > 0 {
+            encoded.push(alphabet[((buffer << (5 - bits)) & 0x1f) as usize]);
+        }
+
+        // Crockford's encoding has no padding
+        if parameters.get_bool("padding") && alphabet_name != BASE32_ALPHABET_CROCKFORD {
+            while encoded.len() % 8 != 0 {
+                encoded.push(b'=');
+            }
+        }
+
+        into_string(encoded)
+    }
+}
+
+pub struct OperationDecodeBase32 {}
+
+impl TextOperation for OperationDecodeBase32 {
+    fn get_id(&self) -> &'static str {
+        "FROM_BASE32"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base32 Decode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_BASE32"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        DECODE_BASE32_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let alphabet_name = parameters.get_text("alphabet");
+        let alphabet = base32_alphabet(alphabet_name);
+        let crockford = alphabet_name == BASE32_ALPHABET_CROCKFORD;
+        let mut decoded: Vec<u8> = Vec::with_capacity(input.len() * 5 / 8);
+        let mut buffer: u32 = 0;
+        let mut bits: u32 = 0;
+        let mut padded = false;
+        let mut symbols = 0;
+        let mut last_offset = 0;
+
+        for (offset, symbol) in input.char_indices() {
+            if symbol.is_whitespace() || (crockford && symbol == '-') {
+                continue;
+            } else if symbol == '=' && !crockford {
+                padded = true;
+                continue;
+            }
+
+            // Crockford decoding accepts the letters that are easily mistaken for digits
+            let symbol = match (crockford, symbol.to_ascii_uppercase()) {
+                (true, 'O') => '0',
+                (true, 'I') | (true, 'L') => '1',
+                (_, symbol) => symbol,
+            };
+            let value = match symbol_value(alphabet, symbol) {
+                Some(value) if !padded => value,
+                _ => return Err(TextOperationError::InvalidInputAtOffset(offset)),
+            };
+
+            buffer = ((buffer << 5) | value) & 0xfff;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                decoded.push((buffer >> bits) as u8);
+            }
+            symbols += 1;
+            last_offset = offset;
+        }
+
+        // no number of bytes encodes to these lengths, and the bits left over from the
+        // last symbol are always written as zeros
+        if matches!(symbols % 8, 1 | 3 | 6) || buffer & ((1 << bits) - 1) != 0 {
+            return Err(TextOperationError::InvalidInputAtOffset(last_offset));
+        }
+
+        Ok(TextOperationResult::with_bytes(decoded))
+    }
+}
+
+pub struct OperationEncodeBase58 {}
+
+impl TextOperation for OperationEncodeBase58 {
+    fn get_id(&self) -> &'static str {
+        "TO_BASE58"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base58 Encode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_BASE58"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        BASE58_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let alphabet = base58_alphabet(parameters.get_text("alphabet"));
+
+        // base 58 digits, least significant first
+        let mut digits: Vec<u8> = vec![];
+        for b in input {
+            let mut carry = *b as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+
+        // every leading zero byte is written as the first symbol
+        let leading_zeros = input.iter().take_while(|b| **b == 0).count();
+        let encoded: Vec<u8> = std::iter::repeat_n(alphabet[0], leading_zeros)
+            .chain(digits.iter().rev().map(|digit| alphabet[*digit as usize]))
+            .collect();
+
+        into_string(encoded)
+    }
+}
+
+pub struct OperationDecodeBase58 {}
+
+impl TextOperation for OperationDecodeBase58 {
+    fn get_id(&self) -> &'static str {
+        "FROM_BASE58"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base58 Decode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_BASE58"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        BASE58_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let alphabet = base58_alphabet(parameters.get_text("alphabet"));
+        let encoded = input.trim();
+        let start = input.len() - input.trim_start().len();
+
+        // bytes of the value, least significant first
+        let mut bytes: Vec<u8> = vec![];
+        for (offset, symbol) in encoded.char_indices() {
+            let mut carry = symbol_value(alphabet, symbol)
+                .ok_or(TextOperationError::InvalidInputAtOffset(start + offset))?;
+            for b in bytes.iter_mut() {
+                carry += *b as u32 * 58;
+                *b = (carry & 0xff) as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                bytes.push((carry & 0xff) as u8);
+                carry >>= 8;
+            }
+        }
+
+        let leading_zeros = encoded
+            .bytes()
+            .take_while(|symbol| *symbol == alphabet[0])
+            .count();
+        let decoded: Vec<u8> = std::iter::repeat_n(0, leading_zeros)
+            .chain(bytes.into_iter().rev())
+            .collect();
+
+        Ok(TextOperationResult::with_bytes(decoded))
+    }
+}
+
+pub struct OperationEncodeBase85 {}
+
+impl TextOperation for OperationEncodeBase85 {
+    fn get_id(&self) -> &'static str {
+        "TO_BASE85"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base85 Encode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_BASE85"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        BASE85_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let z85 = parameters.get_text("variant") == BASE85_VARIANT_Z85;
+        if z85 && input.len() % 4 != 0 {
+            return Err(TextOperationError::InvalidInputError(String::from(
+                "Z85 can only encode a multiple of 4 bytes",
+            )));
+        }
+
+        let mut encoded: Vec<u8> = Vec::with_capacity(input.len().div_ceil(4) * 5);
+        for group in input.chunks(4) {
+            let mut padded = [0u8; 4];
+            padded[..group.len()].copy_from_slice(group);
+            let mut value = u32::from_be_bytes(padded);
+
+            // Ascii85 shortens a whole group of zeros to a single 'z'
+            if !z85 && group.len() == 4 && value == 0 {
+                encoded.push(b'z');
+                continue;
+            }
+
+            let mut symbols = [0u8; 5];
+            for symbol in symbols.iter_mut().rev() {
+                let digit = (value % 85) as u8;
+                *symbol = match z85 {
+                    true => Z85_ALPHABET[digit as usize],
+                    false => digit + b'!',
+                };
+                value /= 85;
+            }
+            // a partial group only needs one more symbol than it has bytes
+            encoded.extend_from_slice(&symbols[..group.len() + 1]);
+        }
+
+        into_string(encoded)
+    }
+}
+
+pub struct OperationDecodeBase85 {}
+
+impl TextOperation for OperationDecodeBase85 {
+    fn get_id(&self) -> &'static str {
+        "FROM_BASE85"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base85 Decode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_BASE85"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        BASE85_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let z85 = parameters.get_text("variant") == BASE85_VARIANT_Z85;
+
+        // Adobe's delimiters are optional around Ascii85
+        let mut start = input.len() - input.trim_start().len();
+        let mut encoded = input.trim();
+        if !z85 {
+            if let Some(stripped) = encoded.strip_prefix("<~") {
+                encoded = stripped;
+                start += 2;
+            }
+            encoded = encoded.strip_suffix("~>").unwrap_or(encoded);
+        }
+
+        let mut decoded: Vec<u8> = Vec::with_capacity(encoded.len() * 4 / 5);
+        let mut group: Vec<u32> = Vec::with_capacity(5);
+        let mut group_start: usize = 0;
+
+        for (offset, symbol) in encoded.char_indices() {
+            let offset = start + offset;
+            if symbol.is_whitespace() {
+                continue;
+            } else if symbol == 'z' && !z85 && group.is_empty() {
+                decoded.extend_from_slice(&[0, 0, 0, 0]);
+                continue;
+            }
+
+            let value = match z85 {
+                true => symbol_value(Z85_ALPHABET, symbol),
+                false if ('!'..='u').contains(&symbol) => Some(symbol as u32 - '!' as u32),
+                false => None,
+            };
+            let value = value.ok_or(TextOperationError::InvalidInputAtOffset(offset))?;
+
+            if group.is_empty() {
+                group_start = offset;
+            }
+            group.push(value);
+            if group.len() == 5 {
+                decoded.extend_from_slice(&decode_base85_group(&group, group_start..offset + 1)?);
+                group.clear();
+            }
+        }
+
+        if !group.is_empty() {
+            let group_range = group_start..start + encoded.len();
+            if z85 || group.len() == 1 {
+                return Err(TextOperationError::InvalidInputInRange(
+                    group_range,
+                    String::from("incomplete group"),
+                ));
+            }
+
+            // a partial group is padded with the highest digit and the extra bytes dropped
+            let length = group.len() - 1;
+            group.resize(5, 84);
+            decoded.extend_from_slice(&decode_base85_group(&group, group_range)?[..length]);
+        }
+
+        Ok(TextOperationResult::with_bytes(decoded))
+    }
+}
+
+fn decode_base85_group(
+    group: &[u32],
+    range: std::ops::Range<usize>,
+) -> Result<[u8; 4], TextOperationError> {
+    group
+        .iter()
+        .try_fold(0u32, |value, digit| {
+            value.checked_mul(85)?.checked_add(*digit)
+        })
+        .map(u32::to_be_bytes)
+        .ok_or(TextOperationError::InvalidInputInRange(
+            range,
+            String::from("group is larger than 32 bits"),
+        ))
+}
+
+pub struct OperationEncodeBase45 {}
+
+impl TextOperation for OperationEncodeBase45 {
+    fn get_id(&self) -> &'static str {
+        "TO_BASE45"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base45 Encode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_BASE45"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut encoded: Vec<u8> = Vec::with_capacity(input.len().div_ceil(2) * 3);
+
+        for pair in input.chunks(2) {
+            let mut value = pair
+                .iter()
+                .fold(0usize, |value, b| value * 256 + *b as usize);
+            // two bytes take three symbols and a single trailing byte takes two, least
+            // significant first
+            for _ in 0..pair.len() + 1 {
+                encoded.push(BASE45_ALPHABET[value % 45]);
+                value /= 45;
+            }
+        }
+
+        into_string(encoded)
+    }
+}
+
+pub struct OperationDecodeBase45 {}
+
+impl TextOperation for OperationDecodeBase45 {
+    fn get_id(&self) -> &'static str {
+        "FROM_BASE45"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Base45 Decode"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_BASE45"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut decoded: Vec<u8> = Vec::with_capacity(input.len() * 2 / 3);
+        let mut group: Vec<usize> = Vec::with_capacity(3);
+        let mut group_start: usize = 0;
+
+        // space is part of the alphabet so only line breaks are skipped
+        let symbols = input
+            .char_indices()
+            .filter(|(_, symbol)| *symbol != '\n' && *symbol != '\r');
+        for (offset, symbol) in symbols {
+            let value = symbol_value(BASE45_ALPHABET, symbol)
+                .ok_or(TextOperationError::InvalidInputAtOffset(offset))?;
+
+            if group.is_empty() {
+                group_start = offset;
+            }
+            group.push(value as usize);
+            if group.len() == 3 {
+                let value = group[0] + group[1] * 45 + group[2] * 45 * 45;
+                if value > 0xffff {
+                    return Err(TextOperationError::InvalidInputInRange(
+                        group_start..offset + 1,
+                        String::from("group is larger than two bytes"),
+                    ));
+                }
+                decoded.extend_from_slice(&(value as u16).to_be_bytes());
+                group.clear();
+            }
+        }
+
+        match group.len() {
+            0 => {}
+            2 if group[0] + group[1] * 45 <= 0xff => {
+                decoded.push((group[0] + group[1] * 45) as u8);
+            }
+            _ => {
+                return Err(TextOperationError::InvalidInputInRange(
+                    group_start..input.trim_end().len().max(group_start + 1),
+                    String::from("incomplete group"),
+                ))
+            }
+        }
+
+        Ok(TextOperationResult::with_bytes(decoded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    fn with_choice(operation: &dyn TextOperation, id: &str, value: &str) -> OperationParameters {
+        operation
+            .default_parameters()
+            .with(id, ParameterValue::Text(String::from(value)))
+    }
+
+    #[test]
+    fn it_encodes_base32_alphabets() {
+        let operation = OperationEncodeBase32 {};
+
+        let result = operation.convert("foobar", &operation.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "MZXW6YTBOI======");
+
+        let parameters = with_choice(&operation, "alphabet", "Base32hex");
+        let result = operation.convert("foobar", &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "CPNMUOJ1E8======");
+
+        let parameters = with_choice(&operation, "alphabet", "Crockford");
+        let result = operation.convert("foobar", &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "CSQPYRK1E8");
+    }
+
+    #[test]
+    fn it_decodes_base32() {
+        let operation = OperationDecodeBase32 {};
+
+        let result = operation.convert("mzxw6ytboi======", &operation.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"foobar");
+
+        let parameters = with_choice(&operation, "alphabet", "Crockford");
+        let result = operation.convert("CSQP-YRKI-E8", &parameters);
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_invalid_base32_characters() {
+        let operation = OperationDecodeBase32 {};
+
+        let result = operation.convert("MZXW 6Y1B", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(7..8));
+    }
+
+    #[test]
+    fn it_rejects_impossible_base32_lengths_and_trailing_bits() {
+        let operation = OperationDecodeBase32 {};
+
+        let result = operation.convert("M", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(0..1));
+
+        let result = operation.convert("MZX", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(2..3));
+
+        let result = operation.convert("MZXW6YTBOJ", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(9..10));
+
+        let result = operation.convert("MZXW6YTBOI", &operation.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"foobar");
+    }
+
+    #[test]
+    fn it_round_trips_base58_with_leading_zeros() {
+        let encode = OperationEncodeBase58 {};
+        let decode = OperationDecodeBase58 {};
+
+        let result = encode.convert("Hello World!", &encode.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "2NEpo7TZRRrLZSi2U");
+
+        let encoded = encode
+            .convert_bytes(&[0, 0, 1, 2], &encode.default_parameters())
+            .unwrap()
+            .text_value
+            .unwrap();
+        assert!(encoded.starts_with("11"));
+        let result = decode.convert(&encoded, &decode.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), vec![0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_invalid_base58_characters() {
+        let operation = OperationDecodeBase58 {};
+
+        // zero is not part of the alphabet
+        let result = operation.convert("2NEp0", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(4..5));
+    }
+
+    #[test]
+    fn it_encodes_and_decodes_ascii85() {
+        let encode = OperationEncodeBase85 {};
+        let decode = OperationDecodeBase85 {};
+
+        let result = encode.convert("hello", &encode.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "BOu!rDZ");
+        let result = encode.convert("\0\0\0\0ab", &encode.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "z@:B");
+
+        let result = decode.convert("<~BOu!rDZ~>", &decode.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"hello");
+        let result = decode.convert("z@:B", &decode.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"\0\0\0\0ab");
+    }
+
+    #[test]
+    fn it_encodes_and_decodes_z85() {
+        let encode = OperationEncodeBase85 {};
+        let decode = OperationDecodeBase85 {};
+        let bytes = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+
+        let parameters = with_choice(&encode, "variant", "Z85");
+        let result = encode.convert_bytes(&bytes, &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "HelloWorld");
+
+        let parameters = with_choice(&decode, "variant", "Z85");
+        let result = decode.convert("HelloWorld", &parameters);
+        assert_eq!(result.unwrap().byte_value.unwrap(), bytes);
+
+        let result = decode.convert("Hello~orld", &parameters);
+        assert_eq!(result.unwrap_err().get_range(), Some(5..6));
+    }
+
+    #[test]
+    fn it_encodes_and_decodes_base45() {
+        let encode = OperationEncodeBase45 {};
+        let decode = OperationDecodeBase45 {};
+
+        let result = encode.convert("Hello!!", &encode.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "%69 VD92EX0");
+
+        let result = decode.convert("%69 VD92EX0", &decode.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"Hello!!");
+    }
+
+    #[test]
+    fn it_reports_invalid_base45() {
+        let operation = OperationDecodeBase45 {};
+
+        let result = operation.convert("%69 vD92EX0", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(4..5));
+
+        // the final group decodes to more than 16 bits
+        let result = operation.convert("BB8:::", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(3..6));
+    }
+}
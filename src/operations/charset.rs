@@ -0,0 +1,425 @@
+use encoding_rs::{DecoderResult, EncoderResult, Encoding};
+
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, TextOperation,
+    TextOperationError, TextOperationResult,
+};
+
+const CHARSET_UTF_8: &str = "UTF-8";
+const CHARSET_UTF_16LE: &str = "UTF-16LE";
+const CHARSET_UTF_16BE: &str = "UTF-16BE";
+const CHARSET_UTF_32LE: &str = "UTF-32LE";
+const CHARSET_UTF_32BE: &str = "UTF-32BE";
+const CHARSET_ISO_8859_1: &str = "ISO-8859-1";
+
+pub(super) const CHARSET_PARAMETER: OperationParameter = OperationParameter {
+    id: "charset",
+    name: "Charset",
+    kind: ParameterKind::Choice {
+        options: &[
+            CHARSET_UTF_8,
+            CHARSET_UTF_16LE,
+            CHARSET_UTF_16BE,
+            CHARSET_UTF_32LE,
+            CHARSET_UTF_32BE,
+            CHARSET_ISO_8859_1,
+            "ISO-8859-2",
+            "ISO-8859-3",
+            "ISO-8859-4",
+            "ISO-8859-5",
+            "ISO-8859-6",
+            "ISO-8859-7",
+            "ISO-8859-8",
+            "ISO-8859-10",
+            "ISO-8859-13",
+            "ISO-8859-14",
+            "ISO-8859-15",
+            "ISO-8859-16",
+            "Windows-1250",
+            "Windows-1251",
+            "Windows-1252",
+            "Windows-1253",
+            "Windows-1254",
+            "Windows-1255",
+            "Windows-1256",
+            "Windows-1257",
+            "Windows-1258",
+            "Shift_JIS",
+            "EUC-KR",
+            "GBK",
+            "KOI8-R",
+        ],
+        default: CHARSET_UTF_8,
+    },
+};
+
+const DECODE_CHARSET_PARAMETERS: &[OperationParameter] = &[CHARSET_PARAMETER];
+
+const ENCODE_CHARSET_PARAMETERS: &[OperationParameter] = &[
+    CHARSET_PARAMETER,
+    OperationParameter {
+        id: "bom",
+        name: "Byte order mark",
+        kind: ParameterKind::Bool { default: false },
+    },
+];
+
+/// The byte order mark of the Unicode charsets
+fn byte_order_mark(charset: &str) -> &'static [u8] {
+    match charset {
+        CHARSET_UTF_8 => &[0xef, 0xbb, 0xbf],
+        CHARSET_UTF_16LE => &[0xff, 0xfe],
+        CHARSET_UTF_16BE => &[0xfe, 0xff],
+        CHARSET_UTF_32LE => &[0xff, 0xfe, 0x00, 0x00],
+        CHARSET_UTF_32BE => &[0x00, 0x00, 0xfe, 0xff],
+        _ => &[],
+    }
+}
+
+/// Looks up the legacy charsets, ISO-8859-1 is not included as encoding_rs treats it as
+/// Windows-1252 like browsers do
+fn legacy_encoding(charset: &str) -> Option<&'static Encoding> {
+    match charset {
+        CHARSET_ISO_8859_1 => None,
+        _ => Encoding::for_label(charset.as_bytes()),
+    }
+}
+
+pub struct OperationDecodeCharset {}
+
+impl TextOperation for OperationDecodeCharset {
+    fn get_id(&self) -> &'static str {
+        "FROM_CHARSET"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Charset to UTF-8"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_CHARSET"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        DECODE_CHARSET_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    /// Decodes the bytes, a leading byte order mark of the charset is dropped
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let charset = parameters.get_text("charset");
+        let bom = byte_order_mark(charset);
+        let (start, encoded) = match input.strip_prefix(bom) {
+            Some(encoded) if !bom.is_empty() => (bom.len(), encoded),
+            _ => (0, input),
+        };
+
+        let decoded = decode_text(encoded, charset, start)?;
+
+        Ok(TextOperationResult::with_string(decoded))
+    }
+}
+
+/// Decodes the bytes of a charset, which is matched case insensitively. `start` is where
+/// `encoded` begins in the input.
+pub(super) fn decode_text(
+    encoded: &[u8],
+    charset: &str,
+    start: usize,
+) -> Result<String, TextOperationError> {
+    let charset = charset.to_ascii_uppercase();
+    match charset.as_str() {
+        CHARSET_UTF_32LE | CHARSET_UTF_32BE => {
+            decode_utf32(encoded, charset == CHARSET_UTF_32LE, start)
+        }
+        CHARSET_ISO_8859_1 => Ok(encoded.iter().map(|b| *b as char).collect()),
+        _ => match legacy_encoding(&charset) {
+            Some(encoding) => decode(encoding, encoded, start),
+            None => Err(unknown_charset(&charset)),
+        },
+    }
+}
+
+/// Decodes with encoding_rs, `start` is where `encoded` begins in the input
+fn decode(
+    encoding: &'static Encoding,
+    encoded: &[u8],
+    start: usize,
+) -> Result<String, TextOperationError> {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let capacity = decoder
+        .max_utf8_buffer_length_without_replacement(encoded.len())
+        .unwrap_or(encoded.len() * 3);
+    let mut decoded = String::with_capacity(capacity);
+
+    let (result, read) = decoder.decode_to_string_without_replacement(encoded, &mut decoded, true);
+    match result {
+        DecoderResult::InputEmpty => Ok(decoded),
+        DecoderResult::Malformed(length, consumed) => {
+            let end = start + read - consumed as usize;
+            Err(TextOperationError::InvalidInputInRange(
+                end - length as usize..end,
+                format!("invalid {} sequence", encoding.name()),
+            ))
+        }
+        DecoderResult::OutputFull => Err(TextOperationError::InvalidInputError(String::from(
+            "the decoded text did not fit the buffer",
+        ))),
+    }
+}
+
+fn decode_utf32(
+    encoded: &[u8],
+    little_endian: bool,
+    start: usize,
+) -> Result<String, TextOperationError> {
+    let mut decoded = String::with_capacity(encoded.len() / 4);
+
+    for (index, unit) in encoded.chunks(4).enumerate() {
+        let range = start + index * 4..start + index * 4 + unit.len();
+        let unit: [u8; 4] = unit.try_into().map_err(|_| {
+            TextOperationError::InvalidInputInRange(
+                range.clone(),
+                String::from("incomplete code unit"),
+            )
+        })?;
+        let value = match little_endian {
+            true => u32::from_le_bytes(unit),
+            false => u32::from_be_bytes(unit),
+        };
+        let c = char::from_u32(value).ok_or_else(|| {
+            TextOperationError::InvalidInputInRange(
+                range,
+                format!("{:#x} is not a Unicode scalar value", value),
+            )
+        })?;
+        decoded.push(c);
+    }
+
+    Ok(decoded)
+}
+
+pub struct OperationEncodeCharset {}
+
+impl TextOperation for OperationEncodeCharset {
+    fn get_id(&self) -> &'static str {
+        "TO_CHARSET"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "UTF-8 to Charset"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_CHARSET"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        ENCODE_CHARSET_PARAMETERS
+    }
+
+    /// Encodes the text, characters the charset has no mapping for are reported at their
+    /// offset in the text
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let charset = parameters.get_text("charset");
+        let mut encoded: Vec<u8> = vec![];
+        if parameters.get_bool("bom") {
+            encoded.extend_from_slice(byte_order_mark(charset));
+        }
+
+        encoded.append(&mut encode_text(input, charset)?);
+
+        Ok(TextOperationResult::with_bytes(encoded))
+    }
+}
+
+/// Encodes text in a charset, which is matched case insensitively, without a byte order mark
+pub(super) fn encode_text(input: &str, charset: &str) -> Result<Vec<u8>, TextOperationError> {
+    let charset = charset.to_ascii_uppercase();
+    let mut encoded: Vec<u8> = Vec::with_capacity(input.len());
+
+    match charset.as_str() {
+        CHARSET_UTF_8 => encoded.extend_from_slice(input.as_bytes()),
+        CHARSET_UTF_16LE => encoded.extend(input.encode_utf16().flat_map(u16::to_le_bytes)),
+        CHARSET_UTF_16BE => encoded.extend(input.encode_utf16().flat_map(u16::to_be_bytes)),
+        CHARSET_UTF_32LE => encoded.extend(input.chars().flat_map(|c| (c as u32).to_le_bytes())),
+        CHARSET_UTF_32BE => encoded.extend(input.chars().flat_map(|c| (c as u32).to_be_bytes())),
+        CHARSET_ISO_8859_1 => {
+            for (offset, c) in input.char_indices() {
+                let b = u8::try_from(c as u32)
+                    .map_err(|_| unmappable(c, offset, CHARSET_ISO_8859_1))?;
+                encoded.push(b);
+            }
+        }
+        _ => match legacy_encoding(&charset) {
+            Some(encoding) => encode(encoding, input, &mut encoded)?,
+            None => return Err(unknown_charset(&charset)),
+        },
+    }
+
+    Ok(encoded)
+}
+
+fn encode(
+    encoding: &'static Encoding,
+    input: &str,
+    encoded: &mut Vec<u8>,
+) -> Result<(), TextOperationError> {
+    let mut encoder = encoding.new_encoder();
+    let mut read: usize = 0;
+
+    loop {
+        let remaining = &input[read..];
+        encoded.reserve(
+            encoder
+                .max_buffer_length_from_utf8_without_replacement(remaining.len())
+                .unwrap_or(remaining.len() * 2),
+        );
+        let (result, consumed) =
+            encoder.encode_from_utf8_to_vec_without_replacement(remaining, encoded, true);
+        read += consumed;
+
+        match result {
+            EncoderResult::InputEmpty => return Ok(()),
+            EncoderResult::OutputFull => continue,
+            EncoderResult::Unmappable(c) => {
+                return Err(unmappable(c, read - c.len_utf8(), encoding.name()))
+            }
+        }
+    }
+}
+
+fn unknown_charset(charset: &str) -> TextOperationError {
+    TextOperationError::InvalidInputError(format!("unknown charset {}", charset))
+}
+
+fn unmappable(c: char, offset: usize, charset: &str) -> TextOperationError {
+    TextOperationError::InvalidInputInRange(
+        offset..offset + c.len_utf8(),
+        format!("'{}' can not be encoded in {}", c, charset),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    fn charset(name: &str) -> OperationParameters {
+        OperationParameters::from_schema(ENCODE_CHARSET_PARAMETERS)
+            .with("charset", ParameterValue::Text(String::from(name)))
+    }
+
+    #[test]
+    fn it_decodes_windows_1252() {
+        let operation = OperationDecodeCharset {};
+
+        let result = operation.convert_bytes(b"caf\xe9 \x80", &charset("Windows-1252"));
+        assert_eq!(result.unwrap().text_value.unwrap(), "café €");
+    }
+
+    #[test]
+    fn it_decodes_iso_8859_1_without_windows_1252_characters() {
+        let operation = OperationDecodeCharset {};
+
+        let result = operation.convert_bytes(b"caf\xe9 \x80", &charset("ISO-8859-1"));
+        assert_eq!(result.unwrap().text_value.unwrap(), "café \u{80}");
+    }
+
+    #[test]
+    fn it_round_trips_legacy_charsets() {
+        let encode = OperationEncodeCharset {};
+        let decode = OperationDecodeCharset {};
+
+        for (name, text) in [
+            ("Shift_JIS", "こんにちは"),
+            ("EUC-KR", "안녕하세요"),
+            ("GBK", "你好"),
+            ("KOI8-R", "привет"),
+            ("ISO-8859-7", "γειά"),
+        ] {
+            let bytes = encode
+                .convert(text, &charset(name))
+                .unwrap()
+                .byte_value
+                .unwrap();
+            assert_ne!(bytes, text.as_bytes());
+
+            let result = decode.convert_bytes(&bytes, &charset(name));
+            assert_eq!(result.unwrap().text_value.unwrap(), text);
+        }
+    }
+
+    #[test]
+    fn it_encodes_utf16_and_utf32_with_byte_order_marks() {
+        let operation = OperationEncodeCharset {};
+
+        let parameters = charset("UTF-16LE").with("bom", ParameterValue::Bool(true));
+        let result = operation.convert("A€", &parameters);
+        assert_eq!(
+            result.unwrap().byte_value.unwrap(),
+            b"\xff\xfeA\x00\xac\x20"
+        );
+
+        let result = operation.convert("A", &charset("UTF-32BE"));
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"\x00\x00\x00A");
+    }
+
+    #[test]
+    fn it_strips_the_byte_order_mark_when_decoding() {
+        let operation = OperationDecodeCharset {};
+
+        let result = operation.convert_bytes(b"\xfe\xff\x00A\x20\xac", &charset("UTF-16BE"));
+        assert_eq!(result.unwrap().text_value.unwrap(), "A€");
+
+        let result =
+            operation.convert_bytes(b"\xff\xfe\x00\x00A\x00\x00\x00", &charset("UTF-32LE"));
+        assert_eq!(result.unwrap().text_value.unwrap(), "A");
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_malformed_bytes() {
+        let operation = OperationDecodeCharset {};
+
+        let result = operation.convert_bytes(b"ab\xff", &charset("UTF-8"));
+        assert_eq!(result.unwrap_err().get_range(), Some(2..3));
+
+        let result =
+            operation.convert_bytes(b"\x00\x00\x00A\x00\x11\x00\x00", &charset("UTF-32BE"));
+        assert_eq!(result.unwrap_err().get_range(), Some(4..8));
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_unmappable_characters() {
+        let operation = OperationEncodeCharset {};
+
+        let result = operation.convert("ab€", &charset("KOI8-R"));
+        assert_eq!(result.unwrap_err().get_range(), Some(2..5));
+
+        let result = operation.convert("é€", &charset("ISO-8859-1"));
+        assert_eq!(result.unwrap_err().get_range(), Some(2..5));
+    }
+}
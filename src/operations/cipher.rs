@@ -0,0 +1,780 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, StreamConverter,
+    TextOperation, TextOperationError, TextOperationResult,
+};
+
+const KEY_FORMAT_TEXT: &str = "Text";
+const KEY_FORMAT_HEX: &str = "Hex";
+const KEY_FORMAT_BASE64: &str = "Base64";
+
+const CAESAR_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "shift",
+    name: "Shift",
+    kind: ParameterKind::Integer {
+        min: 0,
+        max: 25,
+        default: 3,
+    },
+}];
+
+const VIGENERE_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "key",
+    name: "Key",
+    kind: ParameterKind::Text { default: "" },
+}];
+
+const XOR_PARAMETERS: &[OperationParameter] = &[
+    OperationParameter {
+        id: "key",
+        name: "Key",
+        kind: ParameterKind::Text { default: "" },
+    },
+    OperationParameter {
+        id: "key_format",
+        name: "Key format",
+        kind: ParameterKind::Choice {
+            options: &[KEY_FORMAT_TEXT, KEY_FORMAT_HEX, KEY_FORMAT_BASE64],
+            default: KEY_FORMAT_TEXT,
+        },
+    },
+];
+
+const XOR_BRUTE_FORCE_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "results",
+    name: "Results",
+    kind: ParameterKind::Integer {
+        min: 1,
+        max: 256,
+        default: 10,
+    },
+}];
+
+/// How often each letter appears in English text, in percent
+const ENGLISH_LETTER_FREQUENCIES: [f64; 26] = [
+    8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, 6.7, 7.5, 1.9, 0.095, 6.0,
+    6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074,
+];
+
+/// Shifts ASCII letters forward through the alphabet, moving to the next shift in `shifts`
+/// after every letter. Everything else is passed through unchanged, so UTF-8 stays valid.
+struct ShiftCipher {
+    shifts: Vec<u8>,
+    position: usize,
+}
+
+impl ShiftCipher {
+    fn new(shifts: Vec<u8>) -> Self {
+        ShiftCipher {
+            shifts,
+            position: 0,
+        }
+    }
+
+    /// Shifts that undo the shifts of `self`
+    fn inverted(self) -> Self {
+        ShiftCipher::new(self.shifts.iter().map(|shift| (26 - shift) % 26).collect())
+    }
+
+    /// A shift for every letter of the key, `a` leaves text unchanged
+    fn with_key(key: &str) -> Self {
+        ShiftCipher::new(
+            key.bytes()
+                .filter(u8::is_ascii_alphabetic)
+                .map(|b| b.to_ascii_lowercase() - b'a')
+                .collect(),
+        )
+    }
+
+    fn convert_text(mut self, input: &str) -> TextOperationResult {
+        let mut output = Vec::with_capacity(input.len());
+        self.apply(input.as_bytes(), &mut output);
+        // only ASCII letters were replaced, by other ASCII letters
+        TextOperationResult::with_string(String::from_utf8(output).unwrap_or_default())
+    }
+
+    fn apply(&mut self, input: &[u8], output: &mut Vec<u8>) {
+        for b in input {
+            let base = match b {
+                b'a'..=b'z' => b'a',
+                b'A'..=b'Z' => b'A',
+                _ => {
+                    output.push(*b);
+                    continue;
+                }
+            };
+            let shift = match self.shifts.is_empty() {
+                true => 0,
+                false => self.shifts[self.position % self.shifts.len()],
+            };
+            output.push(base + (b - base + shift) % 26);
+            self.position += 1;
+        }
+    }
+}
+
+impl StreamConverter for ShiftCipher {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        self.apply(input, output);
+        Ok(())
+    }
+
+    fn finish(&mut self, _output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        Ok(())
+    }
+}
+
+/// Runs `stream` over all of `input`, so bytes that are not UTF-8 pass through unchanged the
+/// same way they do when streaming
+fn convert_whole(
+    stream: &mut dyn StreamConverter,
+    input: &[u8],
+) -> Result<TextOperationResult, TextOperationError> {
+    let mut output = Vec::with_capacity(input.len());
+    stream.update(input, &mut output)?;
+    stream.finish(&mut output)?;
+    Ok(match String::from_utf8(output) {
+        Ok(output) => TextOperationResult::with_string(output),
+        Err(e) => TextOperationResult::with_bytes(e.into_bytes()),
+    })
+}
+
+/// Replaces every byte on its own, used for ciphers that map ASCII to ASCII
+struct ByteSubstitution {
+    substitute: fn(u8) -> u8,
+}
+
+impl ByteSubstitution {
+    fn convert_text(&self, input: &str) -> TextOperationResult {
+        let output: Vec<u8> = input.bytes().map(self.substitute).collect();
+        // only ASCII bytes were replaced, by other ASCII bytes
+        TextOperationResult::with_string(String::from_utf8(output).unwrap_or_default())
+    }
+}
+
+impl StreamConverter for ByteSubstitution {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        output.extend(input.iter().map(|b| (self.substitute)(*b)));
+        Ok(())
+    }
+
+    fn finish(&mut self, _output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        Ok(())
+    }
+}
+
+fn rot47(b: u8) -> u8 {
+    match b {
+        b'!'..=b'~' => b'!' + (b - b'!' + 47) % 94,
+        _ => b,
+    }
+}
+
+fn atbash(b: u8) -> u8 {
+    match b {
+        b'a'..=b'z' => b'z' - (b - b'a'),
+        b'A'..=b'Z' => b'Z' - (b - b'A'),
+        _ => b,
+    }
+}
+
+pub struct OperationRot13 {}
+
+impl TextOperation for OperationRot13 {
+    fn get_id(&self) -> &'static str {
+        "ROT13"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "ROT13"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("ROT13"))
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(ShiftCipher::new(vec![13]).convert_text(input))
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_whole(&mut ShiftCipher::new(vec![13]), input)
+    }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(ShiftCipher::new(vec![13])))
+    }
+}
+
+pub struct OperationRot47 {}
+
+impl TextOperation for OperationRot47 {
+    fn get_id(&self) -> &'static str {
+        "ROT47"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "ROT47"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("ROT47"))
+    }
+
+    /// Rotates all printable ASCII characters except space by 47
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(ByteSubstitution { substitute: rot47 }.convert_text(input))
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_whole(&mut ByteSubstitution { substitute: rot47 }, input)
+    }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(ByteSubstitution { substitute: rot47 }))
+    }
+}
+
+pub struct OperationCaesarEncrypt {}
+
+impl TextOperation for OperationCaesarEncrypt {
+    fn get_id(&self) -> &'static str {
+        "CAESAR_ENCRYPT"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Caesar Encrypt"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("CAESAR_DECRYPT"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        CAESAR_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(caesar(parameters).convert_text(input))
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_whole(&mut caesar(parameters), input)
+    }
+
+    fn get_stream(&self, parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(caesar(parameters)))
+    }
+}
+
+pub struct OperationCaesarDecrypt {}
+
+impl TextOperation for OperationCaesarDecrypt {
+    fn get_id(&self) -> &'static str {
+        "CAESAR_DECRYPT"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Caesar Decrypt"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("CAESAR_ENCRYPT"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        CAESAR_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(caesar(parameters).inverted().convert_text(input))
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_whole(&mut caesar(parameters).inverted(), input)
+    }
+
+    fn get_stream(&self, parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(caesar(parameters).inverted()))
+    }
+}
+
+fn caesar(parameters: &OperationParameters) -> ShiftCipher {
+    ShiftCipher::new(vec![parameters.get_integer("shift").rem_euclid(26) as u8])
+}
+
+pub struct OperationAtbash {}
+
+impl TextOperation for OperationAtbash {
+    fn get_id(&self) -> &'static str {
+        "ATBASH"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Atbash"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("ATBASH"))
+    }
+
+    /// Mirrors the alphabet so `a` becomes `z`, `b` becomes `y` and so on
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(ByteSubstitution { substitute: atbash }.convert_text(input))
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_whole(&mut ByteSubstitution { substitute: atbash }, input)
+    }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(ByteSubstitution { substitute: atbash }))
+    }
+}
+
+pub struct OperationVigenereEncrypt {}
+
+impl TextOperation for OperationVigenereEncrypt {
+    fn get_id(&self) -> &'static str {
+        "VIGENERE_ENCRYPT"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Vigenère Encrypt"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("VIGENERE_DECRYPT"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        VIGENERE_PARAMETERS
+    }
+
+    /// Shifts each letter by the next letter of the key, characters in the key that are not
+    /// letters are ignored
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(ShiftCipher::with_key(parameters.get_text("key")).convert_text(input))
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_whole(
+            &mut ShiftCipher::with_key(parameters.get_text("key")),
+            input,
+        )
+    }
+
+    fn get_stream(&self, parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(ShiftCipher::with_key(parameters.get_text("key"))))
+    }
+}
+
+pub struct OperationVigenereDecrypt {}
+
+impl TextOperation for OperationVigenereDecrypt {
+    fn get_id(&self) -> &'static str {
+        "VIGENERE_DECRYPT"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Vigenère Decrypt"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("VIGENERE_ENCRYPT"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        VIGENERE_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let cipher = ShiftCipher::with_key(parameters.get_text("key")).inverted();
+        Ok(cipher.convert_text(input))
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_whole(
+            &mut ShiftCipher::with_key(parameters.get_text("key")).inverted(),
+            input,
+        )
+    }
+
+    fn get_stream(&self, parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        let cipher = ShiftCipher::with_key(parameters.get_text("key")).inverted();
+        Some(Box::new(cipher))
+    }
+}
+
+/// XORs the input with a key that is repeated for as long as the input
+struct RepeatingXor {
+    key: Vec<u8>,
+    position: usize,
+}
+
+impl RepeatingXor {
+    fn new(parameters: &OperationParameters) -> Result<Self, TextOperationError> {
+        let key = parameters.get_text("key");
+        let key = match parameters.get_text("key_format") {
+            KEY_FORMAT_HEX => decode_hex_key(key)?,
+            KEY_FORMAT_BASE64 => STANDARD
+                .decode(key.trim())
+                .map_err(|_| TextOperationError::InvalidInputError(String::from("invalid key")))?,
+            _ => key.as_bytes().to_vec(),
+        };
+        Ok(RepeatingXor { key, position: 0 })
+    }
+}
+
+impl StreamConverter for RepeatingXor {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        if self.key.is_empty() {
+            output.extend_from_slice(input);
+            return Ok(());
+        }
+        for b in input {
+            output.push(b ^ self.key[self.position % self.key.len()]);
+            self.position += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, _output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        Ok(())
+    }
+}
+
+/// Reads a key written as hex digits, spaces and a `0x` prefix are allowed
+fn decode_hex_key(key: &str) -> Result<Vec<u8>, TextOperationError> {
+    let digits: String = key.split_whitespace().collect();
+    let digits = digits
+        .strip_prefix("0x")
+        .or(digits.strip_prefix("0X"))
+        .unwrap_or(&digits);
+
+    if digits.len() % 2 != 0 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(TextOperationError::InvalidInputError(String::from(
+            "invalid key",
+        )));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| {
+            u8::from_str_radix(&digits[index..index + 2], 16)
+                .map_err(|_| TextOperationError::InvalidInputError(String::from("invalid key")))
+        })
+        .collect()
+}
+
+pub struct OperationXor {}
+
+impl TextOperation for OperationXor {
+    fn get_id(&self) -> &'static str {
+        "XOR"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "XOR"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("XOR"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        XOR_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut output = Vec::with_capacity(input.len());
+        RepeatingXor::new(parameters)?.update(input, &mut output)?;
+        Ok(TextOperationResult::with_bytes(output))
+    }
+
+    fn get_stream(&self, parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        match RepeatingXor::new(parameters) {
+            Ok(xor) => Some(Box::new(xor)),
+            Err(_) => None,
+        }
+    }
+}
+
+pub struct OperationXorBruteForce {}
+
+impl TextOperation for OperationXorBruteForce {
+    fn get_id(&self) -> &'static str {
+        "XOR_BRUTE_FORCE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "XOR Brute Force"
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        XOR_BRUTE_FORCE_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    /// Tries every single byte key and lists the results that look most like English first
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let results = parameters.get_integer("results").clamp(1, 256) as usize;
+
+        // only the scores are kept, decrypting is repeated for the keys that are shown
+        let mut candidates: Vec<(u8, f64)> = (0..=255u8)
+            .map(|key| (key, english_score(input.iter().map(|b| b ^ key))))
+            .collect();
+        // stable, so keys with the same score stay in order
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut output = String::from("Key   Score  Text\n");
+        for (key, score) in candidates.iter().take(results) {
+            let text: String = input.iter().map(|b| preview_char(b ^ key)).collect();
+            output.push_str(&format!("0x{:02x}  {:5.1}  {}\n", key, score, text));
+        }
+
+        Ok(TextOperationResult::with_string(output))
+    }
+}
+
+/// Scores how much `text` looks like English, from the average frequency of its characters
+fn english_score(text: impl ExactSizeIterator<Item = u8>) -> f64 {
+    let length = text.len();
+    let total: f64 = text
+        .map(|b| match b {
+            b'a'..=b'z' => ENGLISH_LETTER_FREQUENCIES[(b - b'a') as usize],
+            b'A'..=b'Z' => ENGLISH_LETTER_FREQUENCIES[(b - b'A') as usize] * 0.5,
+            b' ' => 13.0,
+            b'0'..=b'9' | b'.' | b',' | b'\'' | b'"' | b'\n' => 1.0,
+            b'!'..=b'~' | b'\t' | b'\r' => 0.0,
+            _ => -20.0,
+        })
+        .sum();
+    total / length.max(1) as f64
+}
+
+/// How a decrypted byte is shown, so every result stays on a single line
+fn preview_char(b: u8) -> char {
+    match b {
+        0x20..=0x7e => b as char,
+        _ => '.',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    #[test]
+    fn it_rotates_letters_by_13() {
+        let operation = OperationRot13 {};
+
+        let result = operation.convert("Hello, Wörld!", &operation.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "Uryyb, Jöeyq!");
+    }
+
+    #[test]
+    fn it_passes_invalid_utf8_through_like_the_stream() {
+        for operation in [
+            &OperationRot13 {} as &dyn TextOperation,
+            &OperationAtbash {},
+        ] {
+            let parameters = operation.default_parameters();
+            let mut stream = operation.get_stream(&parameters).unwrap();
+            let mut streamed = vec![];
+            stream.update(b"Hi \xff", &mut streamed).unwrap();
+            stream.finish(&mut streamed).unwrap();
+
+            let result = operation.convert_bytes(b"Hi \xff", &parameters);
+            assert_eq!(result.unwrap().byte_value.unwrap(), streamed);
+        }
+    }
+
+    #[test]
+    fn it_rotates_printable_ascii_by_47() {
+        let operation = OperationRot47 {};
+
+        let result = operation.convert("Hello, World! 123", &operation.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "w6==@[ (@C=5P `ab");
+    }
+
+    #[test]
+    fn it_shifts_letters_with_caesar_and_back() {
+        let encrypt = OperationCaesarEncrypt {};
+        let decrypt = OperationCaesarDecrypt {};
+        let parameters = encrypt
+            .default_parameters()
+            .with("shift", ParameterValue::Integer(23));
+
+        let result = encrypt.convert("The quick brown fox, XYZ", &parameters);
+        let encrypted = result.unwrap().text_value.unwrap();
+        assert_eq!(encrypted, "Qeb nrfzh yoltk clu, UVW");
+
+        let result = decrypt.convert(&encrypted, &parameters);
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "The quick brown fox, XYZ"
+        );
+    }
+
+    #[test]
+    fn it_mirrors_the_alphabet_with_atbash() {
+        let operation = OperationAtbash {};
+
+        let result = operation.convert("Wizard of Oz", &operation.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "Draziw lu La");
+    }
+
+    #[test]
+    fn it_encrypts_and_decrypts_with_vigenere() {
+        let encrypt = OperationVigenereEncrypt {};
+        let decrypt = OperationVigenereDecrypt {};
+        let parameters = encrypt
+            .default_parameters()
+            .with("key", ParameterValue::Text(String::from("Lemon")));
+
+        let result = encrypt.convert("Attack at dawn!", &parameters);
+        let encrypted = result.unwrap().text_value.unwrap();
+        assert_eq!(encrypted, "Lxfopv ef rnhr!");
+
+        let result = decrypt.convert(&encrypted, &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "Attack at dawn!");
+    }
+
+    #[test]
+    fn it_xors_with_keys_in_each_format() {
+        let operation = OperationXor {};
+
+        for (key, format) in [("key", "Text"), ("6b 65 79", "Hex"), ("a2V5", "Base64")] {
+            let parameters = operation
+                .default_parameters()
+                .with("key", ParameterValue::Text(String::from(key)))
+                .with("key_format", ParameterValue::Text(String::from(format)));
+            let result = operation.convert_bytes(b"abcd", &parameters);
+            assert_eq!(result.unwrap().byte_value.unwrap(), b"\x0a\x07\x1a\x0f");
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_hex_keys() {
+        let operation = OperationXor {};
+        for key in ["abc", "aéb", "0xzz"] {
+            let parameters = operation
+                .default_parameters()
+                .with("key", ParameterValue::Text(String::from(key)))
+                .with("key_format", ParameterValue::Text(String::from("Hex")));
+
+            assert!(operation.convert_bytes(b"abcd", &parameters).is_err());
+        }
+    }
+
+    #[test]
+    fn it_ranks_the_english_result_of_a_single_byte_xor_first() {
+        let operation = OperationXorBruteForce {};
+        let input: Vec<u8> = b"The quick brown fox jumps over the lazy dog"
+            .iter()
+            .map(|b| b ^ 0x2a)
+            .collect();
+
+        let result = operation.convert_bytes(&input, &operation.default_parameters());
+        let output = result.unwrap().text_value.unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 11);
+        assert!(lines[1].starts_with("0x2a"));
+        assert!(lines[1].ends_with("  The quick brown fox jumps over the lazy dog"));
+    }
+}
@@ -0,0 +1,156 @@
+use std::io::{Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, TextOperation,
+    TextOperationError, TextOperationResult,
+};
+
+const GZIP_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "level",
+    name: "Level",
+    kind: ParameterKind::Integer {
+        min: 0,
+        max: 9,
+        default: 6,
+    },
+}];
+
+pub struct OperationGzipCompress {}
+
+impl TextOperation for OperationGzipCompress {
+    fn get_id(&self) -> &'static str {
+        "GZIP"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Gzip Compress"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("GUNZIP"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        GZIP_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let level = parameters.get_integer("level").clamp(0, 9) as u32;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
+
+        encoder
+            .write_all(input)
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
+        let compressed = encoder
+            .finish()
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
+
+        Ok(TextOperationResult::with_bytes(compressed))
+    }
+}
+
+pub struct OperationGzipDecompress {}
+
+impl TextOperation for OperationGzipDecompress {
+    fn get_id(&self) -> &'static str {
+        "GUNZIP"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Gzip Decompress"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("GZIP"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        if !input.starts_with(&[0x1f, 0x8b]) {
+            return Err(TextOperationError::InvalidInputInRange(
+                0..input.len().min(2),
+                String::from("missing gzip header"),
+            ));
+        }
+
+        let mut decompressed: Vec<u8> = vec![];
+        GzDecoder::new(input)
+            .read_to_end(&mut decompressed)
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
+
+        Ok(TextOperationResult::with_bytes(decompressed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_through_gzip() {
+        let compress = OperationGzipCompress {};
+        let decompress = OperationGzipDecompress {};
+
+        let compressed = compress
+            .convert("some text to compress", &compress.default_parameters())
+            .unwrap()
+            .byte_value
+            .unwrap();
+        assert_eq!(compressed[0..2], [0x1f, 0x8b]);
+
+        let result = decompress.convert_bytes(&compressed, &decompress.default_parameters());
+        assert_eq!(
+            result.unwrap().byte_value.unwrap(),
+            b"some text to compress"
+        );
+    }
+
+    #[test]
+    fn it_rejects_data_without_a_gzip_header() {
+        let operation = OperationGzipDecompress {};
+
+        let result = operation.convert("not gzip", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(0..2));
+    }
+}
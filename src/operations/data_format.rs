@@ -0,0 +1,506 @@
+use serde::ser::{Error, SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Number, Value};
+
+use super::json::{error_at_line, parse_json};
+use super::text_operation::{
+    OperationParameter, OperationParameters, ParameterKind, TextOperation, TextOperationError,
+    TextOperationResult,
+};
+
+const FORMAT_JSON: &str = "JSON";
+const FORMAT_YAML: &str = "YAML";
+const FORMAT_TOML: &str = "TOML";
+const FORMAT_RON: &str = "RON";
+
+const FORMATS: &[&str] = &[FORMAT_JSON, FORMAT_YAML, FORMAT_TOML, FORMAT_RON];
+
+const TO_JSON_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "from",
+    name: "From",
+    kind: ParameterKind::Choice {
+        options: FORMATS,
+        default: FORMAT_YAML,
+    },
+}];
+
+const FROM_JSON_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "from",
+    name: "From",
+    kind: ParameterKind::Choice {
+        options: FORMATS,
+        default: FORMAT_JSON,
+    },
+}];
+
+/// Reads a document in any of the formats into a JSON value, the order of keys is kept
+fn parse_document(input: &str, format: &str) -> Result<Value, TextOperationError> {
+    match format {
+        FORMAT_YAML => serde_yaml::from_str(input).map_err(|e| match e.location() {
+            Some(location) => TextOperationError::InvalidInputInRange(
+                location.index()..(location.index() + 1).min(input.len()),
+                e.to_string(),
+            ),
+            None => TextOperationError::InvalidInputError(e.to_string()),
+        }),
+        FORMAT_TOML => {
+            let table: toml::Table = toml::from_str(input).map_err(|e| match e.span() {
+                Some(span) => {
+                    TextOperationError::InvalidInputInRange(span, String::from(e.message()))
+                }
+                None => TextOperationError::InvalidInputError(String::from(e.message())),
+            })?;
+            toml_to_json(toml::Value::Table(table))
+        }
+        FORMAT_RON => {
+            let value: ron::Value = ron::from_str(input).map_err(|e| {
+                error_at_line(input, e.position.line, e.position.col, e.to_string())
+            })?;
+            ron_to_json(value)
+        }
+        _ => parse_json(input),
+    }
+}
+
+/// Converts TOML to JSON, dates and times become strings
+fn toml_to_json(value: toml::Value) -> Result<Value, TextOperationError> {
+    Ok(match value {
+        toml::Value::String(string) => Value::String(string),
+        toml::Value::Integer(integer) => Value::Number(Number::from(integer)),
+        toml::Value::Float(float) => Value::Number(Number::from_f64(float).ok_or_else(|| {
+            TextOperationError::InvalidInputError(format!("{} cannot be converted", float))
+        })?),
+        toml::Value::Boolean(boolean) => Value::Bool(boolean),
+        toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+        toml::Value::Array(array) => Value::Array(
+            array
+                .into_iter()
+                .map(toml_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| Ok((key, toml_to_json(value)?)))
+                .collect::<Result<Map<String, Value>, TextOperationError>>()?,
+        ),
+    })
+}
+
+/// Converts RON to JSON, structs become objects, tuples become arrays and characters become
+/// strings
+fn ron_to_json(value: ron::Value) -> Result<Value, TextOperationError> {
+    Ok(match value {
+        ron::Value::Bool(boolean) => Value::Bool(boolean),
+        ron::Value::Char(char) => Value::String(String::from(char)),
+        ron::Value::String(string) => Value::String(string),
+        ron::Value::Number(ron::Number::Integer(integer)) => Value::Number(Number::from(integer)),
+        ron::Value::Number(ron::Number::Float(float)) => {
+            Value::Number(Number::from_f64(float.get()).ok_or_else(|| {
+                TextOperationError::InvalidInputError(format!(
+                    "{} cannot be converted",
+                    float.get()
+                ))
+            })?)
+        }
+        ron::Value::Option(Some(value)) => ron_to_json(*value)?,
+        ron::Value::Option(None) | ron::Value::Unit => Value::Null,
+        ron::Value::Seq(seq) => {
+            Value::Array(seq.into_iter().map(ron_to_json).collect::<Result<_, _>>()?)
+        }
+        ron::Value::Map(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let key = match ron_to_json(key)? {
+                        Value::String(key) => key,
+                        key @ (Value::Number(_) | Value::Bool(_)) => key.to_string(),
+                        _ => {
+                            return Err(TextOperationError::InvalidInputError(String::from(
+                                "map keys have to be strings, numbers or booleans",
+                            )))
+                        }
+                    };
+                    Ok((key, ron_to_json(value)?))
+                })
+                .collect::<Result<Map<String, Value>, TextOperationError>>()?,
+        ),
+    })
+}
+
+/// Serializes a JSON value with plain numbers, serde_json writes numbers that keep their
+/// precision in a way only it understands
+struct PlainValue<'a>(&'a Value);
+
+impl Serialize for PlainValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Null => serializer.serialize_none(),
+            Value::Bool(boolean) => serializer.serialize_bool(*boolean),
+            Value::Number(number) => match (number.as_i64(), number.as_u64()) {
+                (Some(integer), _) => serializer.serialize_i64(integer),
+                (_, Some(integer)) => serializer.serialize_u64(integer),
+                _ => match number.as_f64().filter(|float| is_exact(number, *float)) {
+                    Some(float) => serializer.serialize_f64(float),
+                    None => Err(S::Error::custom(format!(
+                        "{} cannot be converted without losing precision",
+                        number
+                    ))),
+                },
+            },
+            Value::String(string) => serializer.serialize_str(string),
+            Value::Array(array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+                for element in array {
+                    seq.serialize_element(&PlainValue(element))?;
+                }
+                seq.end()
+            }
+            Value::Object(object) => {
+                let mut map = serializer.serialize_map(Some(object.len()))?;
+                for (key, value) in object {
+                    map.serialize_entry(key, &PlainValue(value))?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// True when `float` has the same value as `number` as it was written
+fn is_exact(number: &Number, float: f64) -> bool {
+    float.is_finite()
+        && decimal_digits(&number.to_string()) == decimal_digits(&format!("{:e}", float))
+}
+
+/// Splits a decimal number into its sign, its significant digits and the exponent of the
+/// first of them, so numbers written in different ways can be compared
+fn decimal_digits(text: &str) -> (bool, String, i64) {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().unwrap_or_default()),
+        None => (text, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    let digits = format!("{}{}", integer, fraction);
+    let significant = digits.trim_start_matches('0');
+    let exponent = exponent + integer.len() as i64 - (digits.len() - significant.len()) as i64;
+    let significant = significant.trim_end_matches('0');
+    match significant.is_empty() {
+        true => (false, String::new(), 0),
+        false => (negative, String::from(significant), exponent),
+    }
+}
+
+/// Finds the path of the first null in `value`, TOML has no way to write one
+fn find_null(value: &Value, path: &str) -> Option<String> {
+    match value {
+        Value::Null => Some(String::from(path)),
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .find_map(|(index, element)| find_null(element, &format!("{}[{}]", path, index))),
+        Value::Object(object) => object
+            .iter()
+            .find_map(|(key, value)| match path.is_empty() {
+                true => find_null(value, key),
+                false => find_null(value, &format!("{}.{}", path, key)),
+            }),
+        _ => None,
+    }
+}
+
+fn write_document(value: &Value, format: &str) -> Result<String, TextOperationError> {
+    match format {
+        FORMAT_YAML => serde_yaml::to_string(&PlainValue(value))
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string())),
+        FORMAT_TOML => {
+            if !value.is_object() {
+                return Err(TextOperationError::InvalidInputError(String::from(
+                    "a TOML document has to be a table",
+                )));
+            }
+            if let Some(path) = find_null(value, "") {
+                return Err(TextOperationError::InvalidInputError(format!(
+                    "TOML has no null, found at {}",
+                    path
+                )));
+            }
+            toml::to_string(&PlainValue(value))
+                .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))
+        }
+        FORMAT_RON => ron::ser::to_string_pretty(&PlainValue(value), ron::ser::PrettyConfig::new())
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string())),
+        _ => serde_json::to_string_pretty(value)
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string())),
+    }
+}
+
+fn convert_document(
+    input: &str,
+    parameters: &OperationParameters,
+    format: &str,
+) -> Result<TextOperationResult, TextOperationError> {
+    let value = parse_document(input, parameters.get_text("from"))?;
+    Ok(TextOperationResult::with_string(write_document(
+        &value, format,
+    )?))
+}
+
+pub struct OperationToJson {}
+
+impl TextOperation for OperationToJson {
+    fn get_id(&self) -> &'static str {
+        "TO_JSON"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Convert to JSON"
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        TO_JSON_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_document(input, parameters, FORMAT_JSON)
+    }
+}
+
+pub struct OperationToYaml {}
+
+impl TextOperation for OperationToYaml {
+    fn get_id(&self) -> &'static str {
+        "TO_YAML"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Convert to YAML"
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        FROM_JSON_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_document(input, parameters, FORMAT_YAML)
+    }
+}
+
+pub struct OperationToToml {}
+
+impl TextOperation for OperationToToml {
+    fn get_id(&self) -> &'static str {
+        "TO_TOML"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Convert to TOML"
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        FROM_JSON_PARAMETERS
+    }
+
+    /// Only tables without nulls can be written, TOML has no null and no top level values
+    /// other than a table
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_document(input, parameters, FORMAT_TOML)
+    }
+}
+
+pub struct OperationToRon {}
+
+impl TextOperation for OperationToRon {
+    fn get_id(&self) -> &'static str {
+        "TO_RON"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Convert to RON"
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        FROM_JSON_PARAMETERS
+    }
+
+    /// Objects are written as RON maps and null as `None`
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        convert_document(input, parameters, FORMAT_RON)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    const JSON: &str =
+        r#"{"name":"wrench","version":2,"ratio":0.5,"tags":["a","b"],"owner":{"id":7}}"#;
+
+    fn from(operation: &dyn TextOperation, format: &str) -> OperationParameters {
+        operation
+            .default_parameters()
+            .with("from", ParameterValue::Text(String::from(format)))
+    }
+
+    #[test]
+    fn it_converts_json_to_yaml_and_back() {
+        let to_yaml = OperationToYaml {};
+        let to_json = OperationToJson {};
+
+        let result = to_yaml.convert(JSON, &to_yaml.default_parameters());
+        let yaml = result.unwrap().text_value.unwrap();
+        assert_eq!(
+            yaml,
+            "name: wrench\nversion: 2\nratio: 0.5\ntags:\n- a\n- b\nowner:\n  id: 7\n"
+        );
+
+        let result = to_json.convert(&yaml, &to_json.default_parameters());
+        let json = result.unwrap().text_value.unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&json).unwrap(),
+            serde_json::from_str::<Value>(JSON).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_converts_json_to_toml_and_back() {
+        let to_toml = OperationToToml {};
+        let to_json = OperationToJson {};
+
+        let result = to_toml.convert(JSON, &to_toml.default_parameters());
+        let toml = result.unwrap().text_value.unwrap();
+        assert_eq!(
+            toml,
+            "name = \"wrench\"\nversion = 2\nratio = 0.5\ntags = [\"a\", \"b\"]\n\n[owner]\nid = 7\n"
+        );
+
+        let result = to_json.convert(&toml, &from(&to_json, FORMAT_TOML));
+        let json = result.unwrap().text_value.unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&json).unwrap(),
+            serde_json::from_str::<Value>(JSON).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_converts_json_to_ron_and_back() {
+        let to_ron = OperationToRon {};
+        let to_json = OperationToJson {};
+
+        let result = to_ron.convert(
+            r#"{"name":"wrench","owner":null}"#,
+            &to_ron.default_parameters(),
+        );
+        let ron = result.unwrap().text_value.unwrap();
+        assert_eq!(ron, "{\n    \"name\": \"wrench\",\n    \"owner\": None,\n}");
+
+        let result = to_json.convert(&ron, &from(&to_json, FORMAT_RON));
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "{\n  \"name\": \"wrench\",\n  \"owner\": null\n}"
+        );
+
+        let result = to_json.convert(
+            "Config(name: \"wrench\", size: (1, 2.5), owner: Some('k'), extra: ())",
+            &from(&to_json, FORMAT_RON),
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&result.unwrap().text_value.unwrap()).unwrap(),
+            serde_json::from_str::<Value>(
+                r#"{"name":"wrench","size":[1,2.5],"owner":"k","extra":null}"#
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn it_converts_toml_dates_to_strings() {
+        let operation = OperationToYaml {};
+
+        let result = operation.convert("released = 2024-05-01", &from(&operation, FORMAT_TOML));
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "released: 2024-05-01\n"
+        );
+    }
+
+    #[test]
+    fn it_explains_what_toml_cannot_represent() {
+        let operation = OperationToToml {};
+
+        let result = operation.convert("[1, 2]", &operation.default_parameters());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            TextOperationError::InvalidInputError(String::from(
+                "a TOML document has to be a table"
+            ))
+            .to_string()
+        );
+
+        let result = operation.convert(r#"{"a": [{"b": null}]}"#, &operation.default_parameters());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            TextOperationError::InvalidInputError(String::from(
+                "TOML has no null, found at a[0].b"
+            ))
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn it_reports_where_parsing_failed() {
+        let operation = OperationToJson {};
+
+        let result = operation.convert("a: 1\nb: [2\n", &operation.default_parameters());
+        assert!(result.unwrap_err().get_range().is_some());
+
+        let result = operation.convert("a = 1\nb = \n", &from(&operation, FORMAT_TOML));
+        assert_eq!(result.unwrap_err().get_range(), Some(10..11));
+
+        let result = operation.convert("{\"a\": 1,\n \"b\" 2}", &from(&operation, FORMAT_RON));
+        assert_eq!(result.unwrap_err().get_range(), Some(14..15));
+    }
+
+    #[test]
+    fn it_refuses_numbers_that_would_lose_precision() {
+        let operation = OperationToYaml {};
+
+        let result = operation.convert(
+            r#"{"a": 1.5e3, "b": -0.25, "c": 18446744073709551615}"#,
+            &operation.default_parameters(),
+        );
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "a: 1500.0\nb: -0.25\nc: 18446744073709551615\n"
+        );
+
+        for number in ["1e400", "12345678901234567890123", "0.10000000000000000001"] {
+            let result = operation.convert(number, &operation.default_parameters());
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("cannot be converted without losing precision"));
+        }
+    }
+}
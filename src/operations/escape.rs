@@ -0,0 +1,656 @@
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::ops::Range;
+use std::str::CharIndices;
+
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, TextOperation,
+    TextOperationError, TextOperationResult,
+};
+
+const SYNTAX_JSON: &str = "JSON";
+const SYNTAX_C: &str = "C/C++";
+const SYNTAX_RUST: &str = "Rust";
+const SYNTAX_JAVA: &str = "Java/JavaScript";
+const SYNTAX_PYTHON: &str = "Python";
+const SYNTAX_HTML: &str = "HTML";
+const SYNTAX_XML: &str = "XML";
+const SYNTAX_CSV: &str = "CSV";
+const SYNTAX_SQL: &str = "SQL";
+const SYNTAX_SHELL: &str = "Shell";
+
+const ESCAPE_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "syntax",
+    name: "Syntax",
+    kind: ParameterKind::Choice {
+        options: &[
+            SYNTAX_JSON,
+            SYNTAX_C,
+            SYNTAX_RUST,
+            SYNTAX_JAVA,
+            SYNTAX_PYTHON,
+            SYNTAX_HTML,
+            SYNTAX_XML,
+            SYNTAX_CSV,
+            SYNTAX_SQL,
+            SYNTAX_SHELL,
+        ],
+        default: SYNTAX_JSON,
+    },
+}];
+
+/// Characters that never need quoting in a POSIX shell word
+const SHELL_SAFE_CHARACTERS: &str = "_@%+=:,./-";
+
+fn invalid(range: Range<usize>, message: &str) -> TextOperationError {
+    TextOperationError::InvalidInputInRange(range, String::from(message))
+}
+
+pub struct OperationEscapeString {}
+
+impl TextOperation for OperationEscapeString {
+    fn get_id(&self) -> &'static str {
+        "ESCAPE_STRING"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Escape String"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("UNESCAPE_STRING"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        ESCAPE_PARAMETERS
+    }
+
+    /// Escapes the text for use inside a string literal of the syntax, CSV fields, SQL
+    /// literals and shell words are quoted as well
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let escaped = match parameters.get_text("syntax") {
+            SYNTAX_HTML => escape_markup(input, "&#39;"),
+            SYNTAX_XML => escape_markup(input, "&apos;"),
+            SYNTAX_CSV => match input.contains([',', '"', '\r', '\n']) {
+                true => format!("\"{}\"", input.replace('"', "\"\"")),
+                false => String::from(input),
+            },
+            SYNTAX_SQL => format!("'{}'", input.replace('\'', "''")),
+            SYNTAX_SHELL => escape_shell(input),
+            syntax => escape_backslashes(input, syntax),
+        };
+        Ok(TextOperationResult::with_string(escaped))
+    }
+}
+
+fn escape_backslashes(input: &str, syntax: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\'' if syntax != SYNTAX_JSON => escaped.push_str("\\'"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ if c.is_control() => escape_code_point(&mut escaped, c, syntax),
+            // Java sources are not always read as UTF-8
+            _ if !c.is_ascii() && syntax == SYNTAX_JAVA => {
+                escape_code_point(&mut escaped, c, syntax)
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes a character as the numeric escape of the syntax
+fn escape_code_point(escaped: &mut String, c: char, syntax: &str) {
+    match syntax {
+        // octal is used as C hex escapes would take the digits that follow
+        SYNTAX_C => {
+            for b in c.to_string().bytes() {
+                let _ = write!(escaped, "\\{:03o}", b);
+            }
+        }
+        SYNTAX_RUST => {
+            let _ = write!(escaped, "\\u{{{:x}}}", c as u32);
+        }
+        SYNTAX_PYTHON if (c as u32) <= 0xff => {
+            let _ = write!(escaped, "\\x{:02x}", c as u32);
+        }
+        _ => {
+            let mut units = [0u16; 2];
+            for unit in c.encode_utf16(&mut units) {
+                let _ = write!(escaped, "\\u{:04x}", unit);
+            }
+        }
+    }
+}
+
+fn escape_markup(input: &str, apostrophe: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str(apostrophe),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_shell(input: &str) -> String {
+    let safe = !input.is_empty()
+        && input
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || SHELL_SAFE_CHARACTERS.contains(c));
+    match safe {
+        true => String::from(input),
+        // a single quote can not appear inside single quotes, so it is closed and reopened
+        false => format!("'{}'", input.replace('\'', "'\\''")),
+    }
+}
+
+pub struct OperationUnescapeString {}
+
+impl TextOperation for OperationUnescapeString {
+    fn get_id(&self) -> &'static str {
+        "UNESCAPE_STRING"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Unescape String"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("ESCAPE_STRING"))
+    }
+
+    /// C escapes can produce any byte, so the result is bytes for every syntax
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        ESCAPE_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let unescaped = match parameters.get_text("syntax") {
+            SYNTAX_HTML => unescape_markup(input, true)?.into_bytes(),
+            SYNTAX_XML => unescape_markup(input, false)?.into_bytes(),
+            SYNTAX_CSV => match input.starts_with('"') {
+                true => unquote_doubled(input, '"')?.into_bytes(),
+                false => input.as_bytes().to_vec(),
+            },
+            SYNTAX_SQL => unquote_doubled(input, '\'')?.into_bytes(),
+            SYNTAX_SHELL => unescape_shell(input)?.into_bytes(),
+            syntax => unescape_backslashes(input, syntax)?,
+        };
+        Ok(TextOperationResult::with_bytes(unescaped))
+    }
+}
+
+/// The offset of the next character, or the end of the input
+fn position(chars: &mut Peekable<CharIndices>, input: &str) -> usize {
+    chars.peek().map_or(input.len(), |(index, _)| *index)
+}
+
+/// Reads up to `max` digits, failing when there are fewer than `min`
+fn read_digits(
+    chars: &mut Peekable<CharIndices>,
+    input: &str,
+    start: usize,
+    radix: u32,
+    (min, max): (usize, usize),
+) -> Result<u32, TextOperationError> {
+    let mut value: u32 = 0;
+    let mut count = 0;
+    while count < max {
+        let digit = match chars.peek().and_then(|(_, c)| c.to_digit(radix)) {
+            Some(digit) => digit,
+            None => break,
+        };
+        chars.next();
+        value = value.saturating_mul(radix).saturating_add(digit);
+        count += 1;
+    }
+
+    match count < min {
+        true => Err(invalid(
+            start..position(chars, input),
+            "too few digits in escape",
+        )),
+        false => Ok(value),
+    }
+}
+
+/// Reads the `{...}` of a `\u{...}` or `\N{...}` escape
+fn read_braced<'a>(
+    chars: &mut Peekable<CharIndices>,
+    input: &'a str,
+    start: usize,
+) -> Result<&'a str, TextOperationError> {
+    let open = match chars.next() {
+        Some((index, '{')) => index + 1,
+        _ => {
+            return Err(invalid(
+                start..position(chars, input),
+                "expected '{' in escape",
+            ))
+        }
+    };
+    for (index, c) in chars.by_ref() {
+        if c == '}' {
+            return Ok(&input[open..index]);
+        }
+    }
+    Err(invalid(start..input.len(), "unterminated escape"))
+}
+
+fn unescape_backslashes(input: &str, syntax: &str) -> Result<Vec<u8>, TextOperationError> {
+    let c_like = syntax == SYNTAX_C || syntax == SYNTAX_PYTHON;
+    let mut unescaped: Vec<u8> = Vec::with_capacity(input.len());
+    let mut chars = input.char_indices().peekable();
+    let mut buffer = [0u8; 4];
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            unescaped.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            continue;
+        }
+        let escape = match chars.next() {
+            Some((_, escape)) => escape,
+            None => return Err(invalid(start..input.len(), "incomplete escape")),
+        };
+        let next_is_digit = chars.peek().is_some_and(|(_, c)| c.is_ascii_digit());
+
+        let code_point: u32 = match escape {
+            '\\' | '"' => escape as u32,
+            '\'' if syntax != SYNTAX_JSON => escape as u32,
+            '/' if syntax == SYNTAX_JSON || syntax == SYNTAX_JAVA => escape as u32,
+            'n' => 0x0a,
+            'r' => 0x0d,
+            't' => 0x09,
+            'b' if syntax != SYNTAX_RUST => 0x08,
+            'f' if syntax != SYNTAX_RUST => 0x0c,
+            'v' if c_like || syntax == SYNTAX_JAVA => 0x0b,
+            'a' if c_like => 0x07,
+            '?' if syntax == SYNTAX_C => escape as u32,
+            '0' if syntax == SYNTAX_RUST || (syntax == SYNTAX_JAVA && !next_is_digit) => 0,
+            '0'..='7' if c_like || syntax == SYNTAX_JAVA => {
+                let value = escape as u32 - '0' as u32;
+                let rest = read_digits(&mut chars, input, start, 8, (0, 2))?;
+                let digits = position(&mut chars, input) - start - 1;
+                let value = value * 8u32.pow(digits as u32 - 1) + rest;
+                if syntax == SYNTAX_C {
+                    let byte = u8::try_from(value).map_err(|_| {
+                        invalid(
+                            start..start + 1 + digits,
+                            "octal escape is larger than a byte",
+                        )
+                    })?;
+                    unescaped.push(byte);
+                    continue;
+                }
+                value
+            }
+            // C reads every hex digit that follows and writes a single byte
+            'x' if syntax == SYNTAX_C => {
+                let value = read_digits(&mut chars, input, start, 16, (1, usize::MAX))?;
+                let byte = u8::try_from(value).map_err(|_| {
+                    invalid(
+                        start..position(&mut chars, input),
+                        "hex escape is larger than a byte",
+                    )
+                })?;
+                unescaped.push(byte);
+                continue;
+            }
+            'x' if syntax != SYNTAX_JSON => {
+                let value = read_digits(&mut chars, input, start, 16, (2, 2))?;
+                if syntax == SYNTAX_RUST && value > 0x7f {
+                    return Err(invalid(
+                        start..position(&mut chars, input),
+                        "hex escape is not ASCII",
+                    ));
+                }
+                value
+            }
+            'u' if syntax == SYNTAX_RUST
+                || (syntax == SYNTAX_JAVA && chars.peek().is_some_and(|(_, c)| *c == '{')) =>
+            {
+                let digits = read_braced(&mut chars, input, start)?;
+                match digits.len() {
+                    1..=6 => u32::from_str_radix(digits, 16).map_err(|_| {
+                        invalid(start..position(&mut chars, input), "invalid hex in escape")
+                    })?,
+                    _ => {
+                        return Err(invalid(
+                            start..position(&mut chars, input),
+                            "expected 1 to 6 hex digits in escape",
+                        ))
+                    }
+                }
+            }
+            'u' if syntax == SYNTAX_JSON || syntax == SYNTAX_JAVA => {
+                let value = read_digits(&mut chars, input, start, 16, (4, 4))?;
+                match value {
+                    0xd800..=0xdbff => read_low_surrogate(&mut chars, input, start, value)?,
+                    _ => value,
+                }
+            }
+            'u' if c_like => read_digits(&mut chars, input, start, 16, (4, 4))?,
+            'U' if c_like => read_digits(&mut chars, input, start, 16, (8, 8))?,
+            'N' if syntax == SYNTAX_PYTHON => {
+                let name = read_braced(&mut chars, input, start)?;
+                let c = unicode_names2::character(name).ok_or_else(|| {
+                    invalid(start..position(&mut chars, input), "unknown character name")
+                })?;
+                c as u32
+            }
+            // an escaped line break continues the string on the next line
+            '\n' if syntax == SYNTAX_RUST => {
+                while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+                continue;
+            }
+            '\n' if syntax == SYNTAX_PYTHON => continue,
+            _ => {
+                return Err(invalid(
+                    start..start + 1 + escape.len_utf8(),
+                    "unknown escape",
+                ))
+            }
+        };
+
+        let c = char::from_u32(code_point).ok_or_else(|| {
+            invalid(
+                start..position(&mut chars, input),
+                "escape is not a Unicode scalar value",
+            )
+        })?;
+        unescaped.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+    }
+
+    Ok(unescaped)
+}
+
+/// JSON and Java write characters outside the BMP as two `\u` escapes
+fn read_low_surrogate(
+    chars: &mut Peekable<CharIndices>,
+    input: &str,
+    start: usize,
+    high: u32,
+) -> Result<u32, TextOperationError> {
+    let end = position(chars, input);
+    if !input[end..].starts_with("\\u") {
+        return Err(invalid(start..end, "unpaired surrogate"));
+    }
+    chars.next();
+    chars.next();
+
+    let low = read_digits(chars, input, start, 16, (4, 4))?;
+    match low {
+        0xdc00..=0xdfff => Ok(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)),
+        _ => Err(invalid(start..position(chars, input), "unpaired surrogate")),
+    }
+}
+
+/// Replaces character references, HTML knows every named entity while XML only has its five
+/// predefined ones and rejects a bare `&`
+fn unescape_markup(input: &str, html: bool) -> Result<String, TextOperationError> {
+    let mut unescaped = String::with_capacity(input.len());
+    let mut position = 0;
+
+    while let Some(found) = input[position..].find('&') {
+        let start = position + found;
+        unescaped.push_str(&input[position..start]);
+
+        let reference = &input[start + 1..];
+        let length = reference
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '#')
+            .unwrap_or(reference.len());
+        let name = &reference[..length];
+        let terminated = reference[length..].starts_with(';');
+        let end = start + 1 + length + terminated as usize;
+
+        if !terminated || name.is_empty() {
+            if html && !name.starts_with('#') {
+                unescaped.push('&');
+                position = start + 1;
+                continue;
+            }
+            return Err(invalid(start..end, "unterminated character reference"));
+        }
+
+        let resolved = match name.strip_prefix('#') {
+            Some(number) => {
+                let (digits, radix) = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => (hex, 16),
+                    None => (number, 10),
+                };
+                u32::from_str_radix(digits, radix)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .map(|c| unescaped.push(c))
+            }
+            None if html => html_escape::NAMED_ENTITIES
+                .binary_search_by(|(entity, _)| (*entity).cmp(name.as_bytes()))
+                .ok()
+                .map(|index| unescaped.push_str(html_escape::NAMED_ENTITIES[index].1)),
+            None => match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => None,
+            }
+            .map(|c| unescaped.push(c)),
+        };
+        if resolved.is_none() {
+            return Err(invalid(start..end, "unknown character reference"));
+        }
+        position = end;
+    }
+
+    unescaped.push_str(&input[position..]);
+    Ok(unescaped)
+}
+
+/// Removes the quotes around CSV fields and SQL literals, where a quote inside is doubled
+fn unquote_doubled(input: &str, quote: char) -> Result<String, TextOperationError> {
+    let inner = match input.len() >= 2 && input.starts_with(quote) && input.ends_with(quote) {
+        true => &input[1..input.len() - 1],
+        false => return Err(invalid(0..input.len(), "the text is not quoted")),
+    };
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.char_indices();
+    while let Some((index, c)) = chars.next() {
+        if c == quote && chars.next().map(|(_, next)| next) != Some(quote) {
+            return Err(invalid(index + 1..index + 2, "quote is not doubled"));
+        }
+        unquoted.push(c);
+    }
+    Ok(unquoted)
+}
+
+/// Reads a single POSIX shell word, removing its quotes and escapes
+fn unescape_shell(input: &str) -> Result<String, TextOperationError> {
+    let mut unescaped = String::with_capacity(input.len());
+    let mut chars = input.char_indices();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next() {
+                    Some((_, '\'')) => break,
+                    Some((_, c)) => unescaped.push(c),
+                    None => return Err(invalid(start..input.len(), "unterminated quote")),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    // inside double quotes a backslash only escapes characters that would
+                    // otherwise be special
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, c @ ('$' | '`' | '"' | '\\'))) => unescaped.push(c),
+                        Some((_, '\n')) => {}
+                        Some((_, c)) => {
+                            unescaped.push('\\');
+                            unescaped.push(c);
+                        }
+                        None => return Err(invalid(start..input.len(), "unterminated quote")),
+                    },
+                    Some((_, c)) => unescaped.push(c),
+                    None => return Err(invalid(start..input.len(), "unterminated quote")),
+                }
+            },
+            '\\' => match chars.next() {
+                Some((_, '\n')) => {}
+                Some((_, c)) => unescaped.push(c),
+                None => return Err(invalid(start..start + 1, "incomplete escape")),
+            },
+            _ if c.is_whitespace() => {
+                return Err(invalid(
+                    start..start + c.len_utf8(),
+                    "unquoted whitespace splits the word",
+                ))
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    Ok(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    fn syntax(name: &str) -> OperationParameters {
+        OperationParameters::from_schema(ESCAPE_PARAMETERS)
+            .with("syntax", ParameterValue::Text(String::from(name)))
+    }
+
+    fn escape(input: &str, name: &str) -> String {
+        let result = OperationEscapeString {}.convert(input, &syntax(name));
+        result.unwrap().text_value.unwrap()
+    }
+
+    fn unescape(input: &str, name: &str) -> Result<Vec<u8>, TextOperationError> {
+        let result = OperationUnescapeString {}.convert(input, &syntax(name));
+        result.map(|result| result.byte_value.unwrap())
+    }
+
+    #[test]
+    fn it_round_trips_every_syntax() {
+        let input = "It's a \"test\" & <more>,\n\ttab\\ \u{1} é 😀";
+
+        for name in [
+            SYNTAX_JSON,
+            SYNTAX_C,
+            SYNTAX_RUST,
+            SYNTAX_JAVA,
+            SYNTAX_PYTHON,
+            SYNTAX_HTML,
+            SYNTAX_XML,
+            SYNTAX_CSV,
+            SYNTAX_SQL,
+            SYNTAX_SHELL,
+        ] {
+            let escaped = escape(input, name);
+            assert_eq!(
+                unescape(&escaped, name).unwrap(),
+                input.as_bytes(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn it_escapes_in_the_style_of_each_syntax() {
+        assert_eq!(escape("a\"\u{1}é", SYNTAX_JSON), "a\\\"\\u0001é");
+        assert_eq!(escape("a\u{1}é", SYNTAX_C), "a\\001é");
+        assert_eq!(escape("a\u{1}é", SYNTAX_RUST), "a\\u{1}é");
+        assert_eq!(escape("a😀", SYNTAX_JAVA), "a\\ud83d\\ude00");
+        assert_eq!(escape("a\u{1}", SYNTAX_PYTHON), "a\\x01");
+        assert_eq!(
+            escape("<a href='x'>", SYNTAX_HTML),
+            "&lt;a href=&#39;x&#39;&gt;"
+        );
+        assert_eq!(escape("a,b", SYNTAX_CSV), "\"a,b\"");
+        assert_eq!(escape("plain", SYNTAX_CSV), "plain");
+        assert_eq!(escape("O'Brien", SYNTAX_SQL), "'O''Brien'");
+        assert_eq!(escape("it's", SYNTAX_SHELL), "'it'\\''s'");
+        assert_eq!(escape("file.txt", SYNTAX_SHELL), "file.txt");
+    }
+
+    #[test]
+    fn it_unescapes_numeric_escapes() {
+        assert_eq!(
+            unescape("\\ud83d\\ude00", SYNTAX_JSON).unwrap(),
+            "😀".as_bytes()
+        );
+        assert_eq!(
+            unescape("\\u{1F600}", SYNTAX_RUST).unwrap(),
+            "😀".as_bytes()
+        );
+        assert_eq!(
+            unescape("\\u{1F600}\\x41", SYNTAX_JAVA).unwrap(),
+            "😀A".as_bytes()
+        );
+        assert_eq!(unescape("\\x41\\101\\xff", SYNTAX_C).unwrap(), b"AA\xff");
+        assert_eq!(
+            unescape("\\xe9\\N{SNOWMAN}", SYNTAX_PYTHON).unwrap(),
+            "é☃".as_bytes()
+        );
+    }
+
+    #[test]
+    fn it_unescapes_character_references() {
+        let result = unescape("&eacute;&#233;&#xE9; AT&T &amp;", SYNTAX_HTML);
+        assert_eq!(result.unwrap(), "ééé AT&T &".as_bytes());
+
+        let result = unescape("&lt;&apos;&gt;", SYNTAX_XML);
+        assert_eq!(result.unwrap(), b"<'>");
+    }
+
+    #[test]
+    fn it_unescapes_shell_words() {
+        let result = unescape("'single '\"double \\$HOME\"\\ escaped", SYNTAX_SHELL);
+        assert_eq!(result.unwrap(), b"single double $HOME escaped");
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_malformed_escapes() {
+        let range = |result: Result<Vec<u8>, TextOperationError>| result.unwrap_err().get_range();
+
+        assert_eq!(range(unescape("ab\\q", SYNTAX_JSON)), Some(2..4));
+        assert_eq!(range(unescape("a\\u12", SYNTAX_JSON)), Some(1..5));
+        assert_eq!(range(unescape("\\ud83d!", SYNTAX_JAVA)), Some(0..6));
+        assert_eq!(range(unescape("\\x80", SYNTAX_RUST)), Some(0..4));
+        assert_eq!(range(unescape("\\x100", SYNTAX_C)), Some(0..5));
+        assert_eq!(range(unescape("a &nope; b", SYNTAX_HTML)), Some(2..8));
+        assert_eq!(range(unescape("AT&T", SYNTAX_XML)), Some(2..4));
+        assert_eq!(range(unescape("'O'Brien'", SYNTAX_SQL)), Some(2..3));
+        assert_eq!(range(unescape("\"a\"b\"", SYNTAX_CSV)), Some(2..3));
+        assert_eq!(range(unescape("ok 'open", SYNTAX_SHELL)), Some(2..3));
+    }
+}
@@ -1,9 +1,127 @@
 use std::num::ParseIntError;
 
-use super::text_operation::{TextOperation, TextOperationError, TextOperationResult};
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, StreamConverter,
+    TextOperation, TextOperationError, TextOperationResult,
+};
+
+const FORMAT_CUSTOM: &str = "Custom";
+const FORMAT_C_ARRAY: &str = "C array";
+const FORMAT_RUST: &str = "Rust byte string";
+const FORMAT_PYTHON: &str = "Python bytes";
+const FORMAT_ESCAPED: &str = "Escaped string";
+
+const CASE_LOWER: &str = "Lower";
+const CASE_UPPER: &str = "Upper";
+
+const TO_HEX_PARAMETERS: &[OperationParameter] = &[
+    OperationParameter {
+        id: "format",
+        name: "Format",
+        kind: ParameterKind::Choice {
+            options: &[
+                FORMAT_CUSTOM,
+                FORMAT_C_ARRAY,
+                FORMAT_RUST,
+                FORMAT_PYTHON,
+                FORMAT_ESCAPED,
+            ],
+            default: FORMAT_CUSTOM,
+        },
+    },
+    OperationParameter {
+        id: "case",
+        name: "Case",
+        kind: ParameterKind::Choice {
+            options: &[CASE_LOWER, CASE_UPPER],
+            default: CASE_LOWER,
+        },
+    },
+    OperationParameter {
+        id: "prefix",
+        name: "Prefix",
+        kind: ParameterKind::Text { default: "" },
+    },
+    OperationParameter {
+        id: "separator",
+        name: "Separator",
+        kind: ParameterKind::Text { default: "" },
+    },
+    OperationParameter {
+        id: "group",
+        name: "Bytes per group",
+        kind: ParameterKind::Integer {
+            min: 1,
+            max: 64,
+            default: 1,
+        },
+    },
+];
+
+/// A format that replaces the custom prefix, separator and grouping
+struct HexPreset {
+    name: &'static str,
+    opening: &'static str,
+    prefix: &'static str,
+    separator: &'static str,
+    closing: &'static str,
+}
+
+const HEX_PRESETS: &[HexPreset] = &[
+    HexPreset {
+        name: FORMAT_C_ARRAY,
+        opening: "{",
+        prefix: "0x",
+        separator: ", ",
+        closing: "}",
+    },
+    HexPreset {
+        name: FORMAT_RUST,
+        opening: "b\"",
+        prefix: "\\x",
+        separator: "",
+        closing: "\"",
+    },
+    HexPreset {
+        name: FORMAT_PYTHON,
+        opening: "b'",
+        prefix: "\\x",
+        separator: "",
+        closing: "'",
+    },
+    HexPreset {
+        name: FORMAT_ESCAPED,
+        opening: "",
+        prefix: "\\x",
+        separator: "",
+        closing: "",
+    },
+];
 
 pub struct OperationToHex {}
 
+impl OperationToHex {
+    fn create_encoder(parameters: &OperationParameters) -> HexEncoder {
+        let format = parameters.get_text("format");
+        let mut encoder = match HEX_PRESETS.iter().find(|preset| preset.name == format) {
+            Some(preset) => HexEncoder {
+                opening: preset.opening,
+                closing: preset.closing,
+                ..HexEncoder::new(preset.prefix, preset.separator)
+            },
+            None => HexEncoder {
+                group: parameters.get_integer("group").max(1) as usize,
+                ..HexEncoder::new(
+                    parameters.get_text("prefix"),
+                    parameters.get_text("separator"),
+                )
+            },
+        };
+        encoder.upper_case = parameters.get_text("case") == CASE_UPPER;
+        encoder
+    }
+}
+
 impl TextOperation for OperationToHex {
     fn get_id(&self) -> &'static str {
         "TO_HEX"
@@ -13,10 +131,36 @@ impl TextOperation for OperationToHex {
         "UTF-8 to Hexadecimal"
     }
 
-    fn convert(&self, input: &str) -> Result<TextOperationResult, TextOperationError> {
-        let hex_string = String::from_iter(input.as_bytes().iter().map(|b| format!("{:x}", b)));
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_HEX"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        TO_HEX_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        OperationToHex::create_encoder(parameters).encode(input)
+    }
 
-        Ok(TextOperationResult::with_string(hex_string))
+    fn get_stream(&self, parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(OperationToHex::create_encoder(parameters)))
     }
 }
 
@@ -31,14 +175,105 @@ impl TextOperation for OperationToLongHex {
         "UTF-8 to Hexadecimal Long"
     }
 
-    fn convert(&self, input: &str) -> Result<TextOperationResult, TextOperationError> {
-        let hex_string =
-            String::from_iter(input.as_bytes().iter().enumerate().map(|(i, b)| match i {
-                0 => format!("{:#x}", b),
-                _ => format!(" {:#x}", b),
-            }));
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_HEX"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        HexEncoder::new("0x", " ").encode(input)
+    }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(HexEncoder::new("0x", " ")))
+    }
+}
+
+/// Writes every byte as two hex digits, putting the prefix before each group of bytes and the
+/// separator between groups
+struct HexEncoder {
+    prefix: String,
+    separator: String,
+    opening: &'static str,
+    closing: &'static str,
+    group: usize,
+    upper_case: bool,
+    started: bool,
+    /// The number of input bytes written so far
+    written: usize,
+}
+
+impl HexEncoder {
+    fn new(prefix: &str, separator: &str) -> Self {
+        HexEncoder {
+            prefix: String::from(prefix),
+            separator: String::from(separator),
+            opening: "",
+            closing: "",
+            group: 1,
+            upper_case: false,
+            started: false,
+            written: 0,
+        }
+    }
+
+    fn encode(mut self, input: &[u8]) -> Result<TextOperationResult, TextOperationError> {
+        let mut output: Vec<u8> = vec![];
+        self.update(input, &mut output)?;
+        self.finish(&mut output)?;
+
+        String::from_utf8(output)
+            .map(TextOperationResult::with_string)
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))
+    }
+
+    fn start(&mut self, output: &mut Vec<u8>) {
+        if !self.started {
+            output.extend_from_slice(self.opening.as_bytes());
+            self.started = true;
+        }
+    }
+}
+
+impl StreamConverter for HexEncoder {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        self.start(output);
+        for b in input {
+            if self.written % self.group == 0 {
+                if self.written > 0 {
+                    output.extend_from_slice(self.separator.as_bytes());
+                }
+                output.extend_from_slice(self.prefix.as_bytes());
+            }
+            let digits = match self.upper_case {
+                true => format!("{:02X}", b),
+                false => format!("{:02x}", b),
+            };
+            output.extend_from_slice(digits.as_bytes());
+            self.written += 1;
+        }
+        Ok(())
+    }
 
-        Ok(TextOperationResult::with_string(hex_string))
+    fn finish(&mut self, output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        self.start(output);
+        output.extend_from_slice(self.closing.as_bytes());
+        Ok(())
     }
 }
 
@@ -53,116 +288,298 @@ impl TextOperation for OperationFromHex {
         "Hexadecimal to UTF-8"
     }
 
-    fn convert(&self, input: &str) -> Result<TextOperationResult, TextOperationError> {
-        let mut code_point: Vec<char> = Vec::with_capacity(4);
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_HEX"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut decoder = HexDecoder::default();
         let mut bytes: Vec<u8> = vec![];
+        decoder.update(input.as_bytes(), &mut bytes)?;
+        decoder.finish(&mut bytes)?;
+
+        Ok(TextOperationResult::with_bytes(bytes))
+    }
 
-        for (index, value) in input.chars().filter(|c| !c.is_whitespace()).enumerate() {
-            if !"ABCDEFabcdef0123456789 x".contains(value) {
-                return Err(TextOperationError::InvalidInputAtOffset(index));
-            } else if value == ' ' {
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(HexDecoder::default()))
+    }
+}
+
+/// Parses pairs of hex digits, optionally prefixed with `0x` or `\\x`, ignoring whitespace and
+/// the punctuation of C arrays and Rust or Python byte strings
+#[derive(Default)]
+struct HexDecoder {
+    code_point: Vec<u8>,
+    code_point_start: usize,
+    /// A backslash was read, which has to be followed by `x`
+    escaped: bool,
+    /// The number of input bytes seen so far
+    offset: usize,
+}
+
+impl StreamConverter for HexDecoder {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        for (index, value) in input.iter().enumerate() {
+            let offset = self.offset + index;
+            if self.escaped {
+                if *value != b'x' {
+                    return Err(TextOperationError::InvalidInputAtOffset(offset));
+                }
+                self.escaped = false;
                 continue;
             }
 
-            code_point.push(value);
+            match value {
+                b'\\' => {
+                    self.escaped = true;
+                    continue;
+                }
+                // the `b` of a byte string is read as a digit until its quote follows
+                b'"' | b'\'' => {
+                    if self.code_point == b"b" && self.code_point_start + 1 == offset {
+                        self.code_point.clear();
+                    }
+                    continue;
+                }
+                b'{' | b'}' | b'[' | b']' | b',' | b';' => continue,
+                _ if value.is_ascii_whitespace() => continue,
+                _ if !value.is_ascii_hexdigit() && !matches!(value, b'x' | b'X') => {
+                    return Err(TextOperationError::InvalidInputAtOffset(offset));
+                }
+                _ => {}
+            }
 
-            if code_point.len() == 4 || (code_point.len() == 2 && code_point[1] != 'x') {
-                let mut parsed_bytes = parse_bytes(&code_point)
-                    .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
-                bytes.append(&mut parsed_bytes);
-                code_point.clear();
+            if self.code_point.is_empty() {
+                self.code_point_start = offset;
+            }
+            self.code_point.push(value.to_ascii_lowercase());
+
+            if self.code_point.len() == 4
+                || (self.code_point.len() == 2 && self.code_point[1] != b'x')
+            {
+                let mut parsed_bytes = parse_bytes(&self.code_point).map_err(|e| {
+                    TextOperationError::InvalidInputInRange(
+                        self.code_point_start..offset + 1,
+                        e.to_string(),
+                    )
+                })?;
+                output.append(&mut parsed_bytes);
+                self.code_point.clear();
             }
         }
 
-        if code_point.len() > 0 {
-            let mut parsed_bytes = parse_bytes(&code_point)
-                .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
-            bytes.append(&mut parsed_bytes);
-            code_point.clear();
-        }
+        self.offset += input.len();
+        Ok(())
+    }
 
-        let value = String::from_utf8(bytes)
-            .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
-        Ok(TextOperationResult::with_string(value))
+    fn finish(&mut self, output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        if !self.code_point.is_empty() {
+            let mut parsed_bytes = parse_bytes(&self.code_point).map_err(|e| {
+                TextOperationError::InvalidInputInRange(
+                    self.code_point_start..self.offset,
+                    e.to_string(),
+                )
+            })?;
+            output.append(&mut parsed_bytes);
+            self.code_point.clear();
+        }
+        Ok(())
     }
 }
 
-fn parse_bytes(chars: &Vec<char>) -> Result<Vec<u8>, ParseIntError> {
+fn parse_bytes(chars: &[u8]) -> Result<Vec<u8>, ParseIntError> {
     if chars.len() < 2 || chars.len() > 4 {
         Ok(Vec::<u8>::new())
     } else if chars.len() < 4 {
         // always only use 2 bytes if there is a third dangler ignore it
-        let byte1 = u8::from_str_radix(String::from_iter(chars[0..2].iter()).as_str(), 16)?;
+        let byte1 = parse_byte(&chars[0..2])?;
         Ok(vec![byte1])
-    } else if chars.len() == 4 && chars[0] == '0' && chars[1] == 'x' {
-        let byte1 = u8::from_str_radix(String::from_iter(chars[2..4].iter()).as_str(), 16)?;
+    } else if chars.len() == 4 && chars[0] == b'0' && chars[1] == b'x' {
+        let byte1 = parse_byte(&chars[2..4])?;
         Ok(vec![byte1])
     } else {
-        let byte1 = u8::from_str_radix(String::from_iter(chars[0..2].iter()).as_str(), 16)?;
-        let byte2 = u8::from_str_radix(String::from_iter(chars[2..4].iter()).as_str(), 16)?;
+        let byte1 = parse_byte(&chars[0..2])?;
+        let byte2 = parse_byte(&chars[2..4])?;
         Ok(vec![byte1, byte2])
     }
 }
 
+fn parse_byte(digits: &[u8]) -> Result<u8, ParseIntError> {
+    u8::from_str_radix(&String::from_utf8_lossy(digits), 16)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::operations::text_operation::ParameterValue;
 
     #[test]
     fn it_converts_bytes_to_string_to_utf8() {
         let bytes: Vec<u8> = String::from("414243").bytes().collect();
 
         let operation = OperationFromHex {};
-        let result = operation.convert_bytes(&bytes);
+        let result = operation.convert_bytes(&bytes, &operation.default_parameters());
 
-        assert_eq!(result.unwrap().text_value.unwrap(), "ABC");
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"ABC");
     }
 
     #[test]
     fn it_converts_hex_string_to_utf8() {
         let operation = OperationFromHex {};
 
-        let result = operation.convert("414243");
-        assert_eq!(result.unwrap().text_value.unwrap(), "ABC");
+        let result = operation.convert("414243", &operation.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"ABC");
     }
 
     #[test]
     fn it_converts_hex_string_with_0x_to_utf8() {
         let operation = OperationFromHex {};
 
-        let result = operation.convert("0x410x420x43");
-        assert_eq!(result.unwrap().text_value.unwrap(), "ABC");
+        let result = operation.convert("0x410x420x43", &operation.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"ABC");
     }
 
     #[test]
     fn it_converts_hex_string_with_whitespace_to_utf8() {
         let operation = OperationFromHex {};
 
-        let result = operation.convert("0x41 42 \n 0x43");
-        assert_eq!(result.unwrap().text_value.unwrap(), "ABC");
+        let result = operation.convert("0x41 42 \n 0x43", &operation.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"ABC");
     }
 
     #[test]
     fn it_ignores_additional_digits_converting_to_utf8() {
         let operation = OperationFromHex {};
 
-        let result = operation.convert("0x41 424");
-        assert_eq!(result.unwrap().text_value.unwrap(), "AB");
+        let result = operation.convert("0x41 424", &operation.default_parameters());
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"AB");
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_invalid_hex_characters() {
+        let operation = OperationFromHex {};
+
+        let result = operation.convert("41 \n 4g", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(6..7));
     }
 
     #[test]
     fn it_converts_utf8_string_to_hex() {
         let operation = OperationToHex {};
 
-        let result = operation.convert("ABC");
+        let result = operation.convert("ABC", &operation.default_parameters());
         assert_eq!(result.unwrap().text_value.unwrap(), "414243");
     }
 
+    #[test]
+    fn it_converts_utf8_string_to_hex_with_prefix_and_separator() {
+        let operation = OperationToHex {};
+        let parameters = operation
+            .default_parameters()
+            .with("prefix", ParameterValue::Text(String::from("0x")))
+            .with("separator", ParameterValue::Text(String::from(", ")));
+
+        let result = operation.convert("ABC", &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "0x41, 0x42, 0x43");
+    }
+
+    #[test]
+    fn it_pads_bytes_to_two_digits() {
+        let operation = OperationToHex {};
+        let parameters = operation
+            .default_parameters()
+            .with("case", ParameterValue::Text(String::from("Upper")));
+
+        let result = operation.convert("\n\u{1b}", &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "0A1B");
+    }
+
+    #[test]
+    fn it_groups_bytes() {
+        let operation = OperationToHex {};
+        let parameters = operation
+            .default_parameters()
+            .with("prefix", ParameterValue::Text(String::from("0x")))
+            .with("separator", ParameterValue::Text(String::from(" ")))
+            .with("group", ParameterValue::Integer(2));
+
+        let result = operation.convert("ABCDE", &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "0x4142 0x4344 0x45");
+    }
+
+    #[test]
+    fn it_formats_hex_presets_and_reads_them_back() {
+        let to_hex = OperationToHex {};
+        let from_hex = OperationFromHex {};
+
+        for (format, expected) in [
+            ("C array", "{0x41, 0x0a}"),
+            ("Rust byte string", "b\"\\x41\\x0a\""),
+            ("Python bytes", "b'\\x41\\x0a'"),
+            ("Escaped string", "\\x41\\x0a"),
+        ] {
+            let parameters = to_hex
+                .default_parameters()
+                .with("format", ParameterValue::Text(String::from(format)));
+            let hex = to_hex
+                .convert("A\n", &parameters)
+                .unwrap()
+                .text_value
+                .unwrap();
+            assert_eq!(hex, expected);
+
+            let result = from_hex.convert(&hex, &from_hex.default_parameters());
+            assert_eq!(result.unwrap().byte_value.unwrap(), b"A\n");
+        }
+    }
+
+    #[test]
+    fn it_formats_empty_input_with_delimiters() {
+        let operation = OperationToHex {};
+        let parameters = operation
+            .default_parameters()
+            .with("format", ParameterValue::Text(String::from("C array")));
+
+        let result = operation.convert("", &parameters);
+        assert_eq!(result.unwrap().text_value.unwrap(), "{}");
+    }
+
+    #[test]
+    fn it_rejects_escapes_other_than_hex() {
+        let operation = OperationFromHex {};
+
+        let result = operation.convert("\\x41\\n", &operation.default_parameters());
+        assert_eq!(result.unwrap_err().get_range(), Some(5..6));
+    }
+
     #[test]
     fn it_converts_utf8_string_to_long_form_hex() {
         let operation = OperationToLongHex {};
 
-        let result = operation.convert("ABC");
+        let result = operation.convert("ABC", &operation.default_parameters());
         assert_eq!(result.unwrap().text_value.unwrap(), "0x41 0x42 0x43");
     }
+
+    #[test]
+    fn it_decodes_hex_split_across_chunks() {
+        let mut stream = OperationFromHex {}
+            .get_stream(&OperationParameters::default())
+            .unwrap();
+        let mut output: Vec<u8> = vec![];
+
+        for chunk in "0x41 4".as_bytes().chunks(1).chain([&b"2 0"[..], b"x43"]) {
+            stream.update(chunk, &mut output).unwrap();
+        }
+        stream.finish(&mut output).unwrap();
+        assert_eq!(output, b"ABC");
+    }
 }
@@ -0,0 +1,586 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, TextOperation,
+    TextOperationError, TextOperationResult,
+};
+
+const DUMP_STYLE_XXD: &str = "xxd";
+const DUMP_STYLE_HEXDUMP: &str = "hexdump -C";
+
+const WORD_ORDER_AS_WRITTEN: &str = "As written";
+const WORD_ORDER_LITTLE_ENDIAN: &str = "Little endian";
+
+/// The most bytes a `*` line may stand for, larger gaps are more likely a damaged address
+/// than a real dump and would use up memory
+const MAX_REPEATED_BYTES: u64 = 1 << 24;
+
+const TO_HEX_DUMP_PARAMETERS: &[OperationParameter] = &[
+    OperationParameter {
+        id: "style",
+        name: "Style",
+        kind: ParameterKind::Choice {
+            options: &[DUMP_STYLE_XXD, DUMP_STYLE_HEXDUMP],
+            default: DUMP_STYLE_XXD,
+        },
+    },
+    OperationParameter {
+        id: "width",
+        name: "Bytes per line",
+        kind: ParameterKind::Integer {
+            min: 1,
+            max: 64,
+            default: 16,
+        },
+    },
+    OperationParameter {
+        id: "group",
+        name: "Group bytes",
+        kind: ParameterKind::Integer {
+            min: 1,
+            max: 64,
+            default: 2,
+        },
+    },
+];
+
+const FROM_HEX_DUMP_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "word_order",
+    name: "Word order",
+    kind: ParameterKind::Choice {
+        options: &[WORD_ORDER_AS_WRITTEN, WORD_ORDER_LITTLE_ENDIAN],
+        default: WORD_ORDER_AS_WRITTEN,
+    },
+}];
+
+/// How a byte is shown in the ASCII gutter of a dump
+fn gutter_char(b: u8) -> char {
+    match b {
+        0x20..=0x7e => b as char,
+        _ => '.',
+    }
+}
+
+pub struct OperationToHexDump {}
+
+impl TextOperation for OperationToHexDump {
+    fn get_id(&self) -> &'static str {
+        "TO_HEX_DUMP"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Hex Dump"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_HEX_DUMP"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        TO_HEX_DUMP_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let width = parameters.get_integer("width").clamp(1, 64) as usize;
+        let group = parameters.get_integer("group").clamp(1, width as i64) as usize;
+        let hexdump = parameters.get_text("style") == DUMP_STYLE_HEXDUMP;
+        let mut dump = String::with_capacity(input.len() * 4 + input.len() / width * 12);
+
+        for (index, line) in input.chunks(width).enumerate() {
+            match hexdump {
+                true => write_hexdump_line(&mut dump, index * width, line, width),
+                false => write_xxd_line(&mut dump, index * width, line, width, group),
+            }
+        }
+        // hexdump finishes with the total length
+        if hexdump && !input.is_empty() {
+            let _ = writeln!(dump, "{:08x}", input.len());
+        }
+
+        Ok(TextOperationResult::with_string(dump))
+    }
+}
+
+/// `00000000: 4865 6c6c 6f0a                           Hello.`
+fn write_xxd_line(dump: &mut String, offset: usize, line: &[u8], width: usize, group: usize) {
+    let _ = write!(dump, "{:08x}: ", offset);
+
+    let hex_start = dump.len();
+    for (index, b) in line.iter().enumerate() {
+        if index > 0 && index % group == 0 {
+            dump.push(' ');
+        }
+        let _ = write!(dump, "{:02x}", b);
+    }
+
+    // pad short lines so the gutter lines up with the full ones
+    let hex_width = width * 2 + (width - 1) / group;
+    let padding = hex_width - (dump.len() - hex_start) + 2;
+    dump.push_str(&" ".repeat(padding));
+    dump.extend(line.iter().map(|b| gutter_char(*b)));
+    dump.push('\n');
+}
+
+/// `00000000  48 65 6c 6c 6f 0a                                 |Hello.|`
+fn write_hexdump_line(dump: &mut String, offset: usize, line: &[u8], width: usize) {
+    let _ = write!(dump, "{:08x}  ", offset);
+
+    for index in 0..width {
+        match line.get(index) {
+            Some(b) => {
+                let _ = write!(dump, "{:02x} ", b);
+            }
+            None => dump.push_str("   "),
+        }
+        // the line is split in half with an extra space
+        if width > 1 && index + 1 == width / 2 {
+            dump.push(' ');
+        }
+    }
+
+    dump.push_str(" |");
+    dump.extend(line.iter().map(|b| gutter_char(*b)));
+    dump.push_str("|\n");
+}
+
+pub struct OperationFromHexDump {}
+
+impl TextOperation for OperationFromHexDump {
+    fn get_id(&self) -> &'static str {
+        "FROM_HEX_DUMP"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Reverse Hex Dump"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_HEX_DUMP"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        FROM_HEX_DUMP_PARAMETERS
+    }
+
+    /// Reads dumps in the xxd, `hexdump -C`, Wireshark, gdb `x/` and `od -t x` layouts. Each
+    /// line may start with an address and end with an ASCII gutter, both are skipped. The
+    /// octal words od writes by default are reported as an error.
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let little_endian = parameters.get_text("word_order") == WORD_ORDER_LITTLE_ENDIAN;
+        let mut bytes: Vec<u8> = vec![];
+        let mut has_addresses = false;
+        let mut first_address: Option<u64> = None;
+        let mut previous_address: Option<u64> = None;
+        let mut previous_line: Vec<u8> = vec![];
+        let mut repeating = false;
+        let mut line_start: usize = 0;
+
+        for line in input.split_inclusive('\n') {
+            let line_offset = line_start;
+            line_start += line.len();
+            let text = line.trim_end_matches(['\n', '\r']);
+
+            // hexdump and od print `*` in place of lines that repeat the one before
+            if text.trim() == "*" {
+                repeating = true;
+                continue;
+            }
+
+            let (address, data_start) = split_address(text, has_addresses);
+            if let Some((address, range)) = address {
+                // od's default layout has octal words that would be read as three bytes each
+                let data = &text[data_start..];
+                let word_start = data_start + data.len() - data.trim_start().len();
+                if is_octal(&text[range.clone()], 7)
+                    && data
+                        .split_whitespace()
+                        .next()
+                        .is_some_and(|word| is_octal(word, 6))
+                {
+                    return Err(TextOperationError::InvalidInputInRange(
+                        line_offset + word_start..line_offset + word_start + 6,
+                        String::from("octal od output cannot be read, dump with od -t x1"),
+                    ));
+                }
+
+                let range = line_offset + range.start..line_offset + range.end;
+                if previous_address.is_some_and(|previous| address < previous) {
+                    return Err(TextOperationError::InvalidInputInRange(
+                        range,
+                        String::from("the address is lower than the one before it"),
+                    ));
+                }
+                has_addresses = true;
+                previous_address = Some(address);
+
+                let first_address = *first_address.get_or_insert(address);
+                let expected = address - first_address;
+                if repeating && !previous_line.is_empty() {
+                    if expected.saturating_sub(bytes.len() as u64) > MAX_REPEATED_BYTES {
+                        return Err(TextOperationError::InvalidInputInRange(
+                            range,
+                            format!(
+                                "the repeated lines stand for more than {} bytes",
+                                MAX_REPEATED_BYTES
+                            ),
+                        ));
+                    }
+                    let expected = expected as usize;
+                    while bytes.len() < expected {
+                        let missing = (expected - bytes.len()).min(previous_line.len());
+                        bytes.extend_from_slice(&previous_line[..missing]);
+                    }
+                }
+            }
+            repeating = false;
+
+            let line_bytes =
+                parse_dump_line(&text[data_start..], line_offset + data_start, little_endian)?;
+            if !line_bytes.is_empty() {
+                bytes.extend_from_slice(&line_bytes);
+                previous_line = line_bytes;
+            }
+        }
+
+        Ok(TextOperationResult::with_bytes(bytes))
+    }
+}
+
+/// Finds the address at the start of a line, returning it with its range in the line and
+/// where the data begins
+fn split_address(text: &str, has_addresses: bool) -> (Option<(u64, Range<usize>)>, usize) {
+    let start = text.len() - text.trim_start().len();
+
+    // xxd and gdb end the address with a colon, gdb may add a symbol before it
+    if let Some(colon) = text.find(':') {
+        let prefix = text[..colon].trim();
+        let (first, rest) = prefix
+            .split_once(char::is_whitespace)
+            .unwrap_or((prefix, ""));
+        let rest = rest.trim();
+        if let Some(address) = parse_address(first) {
+            if rest.is_empty() || (rest.starts_with('<') && rest.ends_with('>')) {
+                return (Some((address, start..start + first.len())), colon + 1);
+            }
+        }
+    }
+
+    // hexdump, Wireshark and od separate the address with whitespace only. Grouped hex without
+    // addresses looks the same, so the first line needs an address that is wider than the
+    // values after it or has an odd number of digits like od's. After that any value that
+    // starts a line is an address, a lone one like the total at the end of hexdump included.
+    let first_end = text[start..]
+        .find(char::is_whitespace)
+        .map_or(text.len(), |end| start + end);
+    let first = &text[start..first_end];
+    let digits = first.trim_start_matches("0x").len();
+    let is_address = match text[first_end..].split_whitespace().next() {
+        _ if has_addresses => true,
+        Some(value) => digits % 2 == 1 || digits > value.trim_start_matches("0x").len(),
+        None => false,
+    };
+    if digits >= 4 && is_address {
+        if let Some(address) = parse_address(first) {
+            return (Some((address, start..first_end)), first_end);
+        }
+    }
+
+    (None, 0)
+}
+
+fn is_octal(token: &str, digits: usize) -> bool {
+    token.len() == digits && token.bytes().all(|b| (b'0'..=b'7').contains(&b))
+}
+
+fn parse_address(token: &str) -> Option<u64> {
+    let digits = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+        .unwrap_or(token);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    // od writes seven octal digits by default
+    let radix = match digits.len() == 7 && digits.chars().all(|c| ('0'..='7').contains(&c)) {
+        true => 8,
+        false => 16,
+    };
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// Parses the hex values of one line, `offset` is where `data` starts in the input
+fn parse_dump_line(
+    data: &str,
+    offset: usize,
+    little_endian: bool,
+) -> Result<Vec<u8>, TextOperationError> {
+    // gutters between delimiters can be cut off directly, `|...|` from hexdump and `>...<`
+    // from od -z
+    let trimmed = data.trim_end();
+    let data = match (trimmed.ends_with('|'), trimmed.ends_with('<')) {
+        (true, _) => &data[..data.find('|').unwrap_or(data.len())],
+        (_, true) => &data[..data.find('>').unwrap_or(data.len())],
+        _ => data,
+    };
+
+    let mut bytes: Vec<u8> = vec![];
+    // the end of each value and how many bytes had been read by then
+    let mut value_ends: Vec<(usize, usize)> = vec![(0, 0)];
+    let mut position = 0;
+    let mut stopped_at: Option<usize> = None;
+
+    while position < data.len() {
+        let start = match data[position..].find(|c: char| !c.is_whitespace()) {
+            Some(start) => position + start,
+            None => break,
+        };
+        let end = data[start..]
+            .find(char::is_whitespace)
+            .map_or(data.len(), |end| start + end);
+        let token = &data[start..end];
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+
+        if digits.is_empty()
+            || digits.len() % 2 != 0
+            || !digits.bytes().all(|b| b.is_ascii_hexdigit())
+        {
+            // point at the first character that is not a hex digit
+            let prefix = token.len() - digits.len();
+            let invalid = digits
+                .find(|c: char| !c.is_ascii_hexdigit())
+                .map_or(0, |index| prefix + index);
+            stopped_at = Some(start + invalid);
+            break;
+        }
+
+        let mut value: Vec<u8> = (0..digits.len())
+            .step_by(2)
+            .filter_map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+            .collect();
+        if little_endian {
+            value.reverse();
+        }
+        bytes.append(&mut value);
+        value_ends.push((end, bytes.len()));
+        position = end;
+    }
+
+    // what follows the values is an ASCII gutter when it shows exactly the bytes before it
+    // after a wider gap, gutter text such as `cafe` may have been read as values so every
+    // earlier end is tried
+    for (end, length) in value_ends.iter().rev() {
+        let tail = data[*end..].trim_end();
+        let gutter: String = bytes[..*length].iter().map(|b| gutter_char(*b)).collect();
+        let gutter = gutter.trim_end();
+
+        if let Some(separator) = tail.strip_suffix(gutter) {
+            if separator.len() >= 2 && separator.trim().is_empty() {
+                bytes.truncate(*length);
+                return Ok(bytes);
+            }
+        }
+    }
+
+    match stopped_at {
+        Some(start) => Err(TextOperationError::InvalidInputAtOffset(offset + start)),
+        None => Ok(bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    fn reverse_dump(dump: &str, parameters: &OperationParameters) -> Vec<u8> {
+        let operation = OperationFromHexDump {};
+        operation
+            .convert(dump, &operation.default_parameters().merged(parameters))
+            .unwrap()
+            .byte_value
+            .unwrap()
+    }
+
+    #[test]
+    fn it_dumps_like_xxd() {
+        let operation = OperationToHexDump {};
+
+        let result = operation.convert(
+            "Hello world, how are you?\0\x01",
+            &operation.default_parameters(),
+        );
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "00000000: 4865 6c6c 6f20 776f 726c 642c 2068 6f77  Hello world, how\n\
+             00000010: 2061 7265 2079 6f75 3f00 01               are you?..\n"
+        );
+    }
+
+    #[test]
+    fn it_dumps_like_hexdump() {
+        let operation = OperationToHexDump {};
+        let parameters = operation
+            .default_parameters()
+            .with("style", ParameterValue::Text(String::from("hexdump -C")));
+
+        let result = operation.convert("Hello world\n", &parameters);
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "00000000  48 65 6c 6c 6f 20 77 6f  72 6c 64 0a              |Hello world.|\n\
+             0000000c\n"
+        );
+    }
+
+    #[test]
+    fn it_reverses_its_own_dumps() {
+        let dump = OperationToHexDump {};
+        let input: Vec<u8> = (0..=255).collect();
+
+        for style in ["xxd", "hexdump -C"] {
+            let parameters = dump
+                .default_parameters()
+                .with("style", ParameterValue::Text(String::from(style)))
+                .with("width", ParameterValue::Integer(12))
+                .with("group", ParameterValue::Integer(4));
+            let text = dump.convert_bytes(&input, &parameters).unwrap();
+
+            let bytes = reverse_dump(&text.text_value.unwrap(), &OperationParameters::default());
+            assert_eq!(bytes, input);
+        }
+    }
+
+    #[test]
+    fn it_reads_gutters_that_look_like_hex() {
+        let bytes = reverse_dump(
+            "00000000: 6361 6665  cafe\n",
+            &OperationParameters::default(),
+        );
+        assert_eq!(bytes, b"cafe");
+    }
+
+    #[test]
+    fn it_expands_repeated_hexdump_lines() {
+        let bytes = reverse_dump(
+            "00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|\n\
+             *\n\
+             00000020  41                                                |A|\n\
+             00000021\n",
+            &OperationParameters::default(),
+        );
+        assert_eq!(bytes.len(), 33);
+        assert_eq!(bytes[32], b'A');
+    }
+
+    #[test]
+    fn it_rejects_large_gaps_and_addresses_that_go_backwards() {
+        let operation = OperationFromHexDump {};
+
+        for (dump, range) in [
+            ("00000000  00\n*\n20000000  00\n", 15..23),
+            ("00000000  00\n*\nffffffffff  00\n", 15..25),
+        ] {
+            let result = operation.convert(dump, &operation.default_parameters());
+            assert_eq!(result.unwrap_err().get_range(), Some(range));
+        }
+
+        let result = operation.convert(
+            "00000010: 4142\n00000000: 4344\n",
+            &operation.default_parameters(),
+        );
+        assert_eq!(result.unwrap_err().get_range(), Some(15..23));
+    }
+
+    #[test]
+    fn it_rejects_octal_od_output() {
+        let operation = OperationFromHexDump {};
+
+        let result = operation.convert(
+            "0000000 062510 066154 005157\n0000006\n",
+            &operation.default_parameters(),
+        );
+        assert_eq!(result.unwrap_err().get_range(), Some(8..14));
+    }
+
+    #[test]
+    fn it_reads_grouped_hex_without_addresses() {
+        let bytes = reverse_dump("4865 6c6c 6f0a", &OperationParameters::default());
+        assert_eq!(bytes, b"Hello\n");
+
+        let bytes = reverse_dump(
+            "0000000 6c6c6548 0a6f\n0000006\n",
+            &OperationParameters::default().with(
+                "word_order",
+                ParameterValue::Text(String::from("Little endian")),
+            ),
+        );
+        assert_eq!(bytes, b"Hello\n");
+    }
+
+    #[test]
+    fn it_reads_wireshark_gdb_and_od_dumps() {
+        let expected = b"Hello world\n";
+
+        let wireshark = "0000   48 65 6c 6c 6f 20 77 6f 72 6c 64 0a   Hello world.";
+        assert_eq!(
+            reverse_dump(wireshark, &OperationParameters::default()),
+            expected
+        );
+
+        let od = "000000 48 65 6c 6c 6f 20 77 6f 72 6c 64 0a  >Hello world.<\n00000c\n";
+        assert_eq!(reverse_dump(od, &OperationParameters::default()), expected);
+
+        let gdb_bytes = "0x7fffffffe3a0:\t0x48\t0x65\t0x6c\t0x6c\t0x6f\t0x20\t0x77\t0x6f\n\
+                         0x7fffffffe3a8:\t0x72\t0x6c\t0x64\t0x0a";
+        assert_eq!(
+            reverse_dump(gdb_bytes, &OperationParameters::default()),
+            expected
+        );
+
+        let gdb_words = "0x555555556004 <msg>:\t0x6c6c6548\t0x6f77206f\t0x0a646c72";
+        let little_endian = OperationParameters::default().with(
+            "word_order",
+            ParameterValue::Text(String::from("Little endian")),
+        );
+        assert_eq!(reverse_dump(gdb_words, &little_endian), expected);
+    }
+
+    #[test]
+    fn it_reports_the_offset_of_invalid_dump_values() {
+        let operation = OperationFromHexDump {};
+
+        let result = operation.convert(
+            "00000000: 4865 6c6c\n00000004: 6f2g 0a",
+            &operation.default_parameters(),
+        );
+        assert_eq!(result.unwrap_err().get_range(), Some(33..34));
+    }
+}
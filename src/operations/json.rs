@@ -0,0 +1,272 @@
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::{Serializer, Value};
+
+use super::text_operation::{
+    OperationParameter, OperationParameters, ParameterKind, TextOperation, TextOperationError,
+    TextOperationResult,
+};
+
+const JSON_FORMAT_PARAMETERS: &[OperationParameter] = &[
+    OperationParameter {
+        id: "indent",
+        name: "Indent",
+        kind: ParameterKind::Integer {
+            min: 0,
+            max: 16,
+            default: 2,
+        },
+    },
+    OperationParameter {
+        id: "tabs",
+        name: "Indent with tabs",
+        kind: ParameterKind::Bool { default: false },
+    },
+];
+
+/// Parses JSON keeping the order of keys and the digits of numbers as they were written
+pub(super) fn parse_json(input: &str) -> Result<Value, TextOperationError> {
+    serde_json::from_str(input).map_err(|e| json_error(input, &e))
+}
+
+/// Points a parse error at the character where it was found, the message includes its line
+/// and column
+fn json_error(input: &str, error: &serde_json::Error) -> TextOperationError {
+    error_at_line(input, error.line(), error.column(), error.to_string())
+}
+
+/// An error for the character at a line and byte column, both counted from 1
+pub(super) fn error_at_line(
+    input: &str,
+    line: usize,
+    column: usize,
+    message: String,
+) -> TextOperationError {
+    let line_start: usize = input
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let offset = (line_start + column.saturating_sub(1)).min(input.len());
+    let end = (offset + 1).min(input.len());
+
+    TextOperationError::InvalidInputInRange(offset..end, message)
+}
+
+/// Writes `value` with one member per line, indented by spaces or by a tab per level
+pub(super) fn write_json(
+    value: &Value,
+    parameters: &OperationParameters,
+) -> Result<String, TextOperationError> {
+    let indent = match parameters.get_bool("tabs") {
+        true => String::from("\t"),
+        false => " ".repeat(parameters.get_integer("indent").clamp(0, 16) as usize),
+    };
+
+    let mut written = vec![];
+    let mut serializer = Serializer::with_formatter(
+        &mut written,
+        PrettyFormatter::with_indent(indent.as_bytes()),
+    );
+    value
+        .serialize(&mut serializer)
+        .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
+
+    // the serializer only writes valid UTF-8
+    Ok(String::from_utf8(written).unwrap_or_default())
+}
+
+fn sort_keys(value: &mut Value) {
+    match value {
+        Value::Object(members) => {
+            members.sort_keys();
+            members.values_mut().for_each(sort_keys);
+        }
+        Value::Array(elements) => elements.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+pub struct OperationJsonBeautify {}
+
+impl TextOperation for OperationJsonBeautify {
+    fn get_id(&self) -> &'static str {
+        "JSON_BEAUTIFY"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "JSON Beautify"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("JSON_MINIFY"))
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        JSON_FORMAT_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let value = parse_json(input)?;
+        Ok(TextOperationResult::with_string(write_json(
+            &value, parameters,
+        )?))
+    }
+}
+
+pub struct OperationJsonMinify {}
+
+impl TextOperation for OperationJsonMinify {
+    fn get_id(&self) -> &'static str {
+        "JSON_MINIFY"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "JSON Minify"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("JSON_BEAUTIFY"))
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let value = parse_json(input)?;
+        let minified = serde_json::to_string(&value)
+            .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
+        Ok(TextOperationResult::with_string(minified))
+    }
+}
+
+pub struct OperationJsonSortKeys {}
+
+impl TextOperation for OperationJsonSortKeys {
+    fn get_id(&self) -> &'static str {
+        "JSON_SORT_KEYS"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "JSON Sort Keys"
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        JSON_FORMAT_PARAMETERS
+    }
+
+    /// Sorts the keys of every object, including objects nested in other values
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut value = parse_json(input)?;
+        sort_keys(&mut value);
+        Ok(TextOperationResult::with_string(write_json(
+            &value, parameters,
+        )?))
+    }
+}
+
+pub struct OperationJsonValidate {}
+
+impl TextOperation for OperationJsonValidate {
+    fn get_id(&self) -> &'static str {
+        "JSON_VALIDATE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "JSON Validate"
+    }
+
+    /// Passes valid JSON through unchanged, so it can guard the steps that follow
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        parse_json(input)?;
+        Ok(TextOperationResult::with_string(String::from(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    #[test]
+    fn it_beautifies_with_spaces_or_tabs() {
+        let operation = OperationJsonBeautify {};
+
+        let result = operation.convert(r#"{"b":[1,{}],"a":null}"#, &operation.default_parameters());
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "{\n  \"b\": [\n    1,\n    {}\n  ],\n  \"a\": null\n}"
+        );
+
+        let parameters = operation
+            .default_parameters()
+            .with("tabs", ParameterValue::Bool(true));
+        let result = operation.convert(r#"{"a":[true]}"#, &parameters);
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "{\n\t\"a\": [\n\t\ttrue\n\t]\n}"
+        );
+    }
+
+    #[test]
+    fn it_minifies_keeping_key_order_and_number_precision() {
+        let operation = OperationJsonMinify {};
+
+        let result = operation.convert(
+            "{\n  \"z\": 12345678901234567890123,\n  \"a\": 0.10000000000000000001\n}",
+            &operation.default_parameters(),
+        );
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            r#"{"z":12345678901234567890123,"a":0.10000000000000000001}"#
+        );
+    }
+
+    #[test]
+    fn it_sorts_keys_recursively() {
+        let operation = OperationJsonSortKeys {};
+        let parameters = operation
+            .default_parameters()
+            .with("indent", ParameterValue::Integer(0));
+
+        let result = operation.convert(r#"{"b":{"y":1,"x":2},"a":[{"d":3,"c":4}]}"#, &parameters);
+        assert_eq!(
+            result.unwrap().text_value.unwrap(),
+            "{\n\"a\": [\n{\n\"c\": 4,\n\"d\": 3\n}\n],\n\"b\": {\n\"x\": 2,\n\"y\": 1\n}\n}"
+        );
+    }
+
+    #[test]
+    fn it_reports_the_line_and_column_of_parse_errors() {
+        let operation = OperationJsonValidate {};
+
+        let result = operation.convert("{\"a\": 1}", &operation.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "{\"a\": 1}");
+
+        let result = operation.convert(
+            "{\n  \"a\": 1,\n  \"b\" 2\n}",
+            &operation.default_parameters(),
+        );
+        let error = result.unwrap_err();
+        assert_eq!(error.get_range(), Some(18..19));
+        match error {
+            TextOperationError::InvalidInputInRange(_, message) => {
+                assert_eq!(message, "expected `:` at line 3 column 7")
+            }
+            _ => panic!("expected an error with a range"),
+        }
+    }
+}
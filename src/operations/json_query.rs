@@ -0,0 +1,713 @@
+use std::cmp::Ordering;
+
+use serde_json::{Number, Value};
+use serde_json_path::JsonPath;
+
+use super::json::parse_json;
+use super::text_operation::{
+    OperationParameter, OperationParameters, ParameterKind, TextOperation, TextOperationError,
+    TextOperationResult,
+};
+
+const QUERY_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "query",
+    name: "Query",
+    kind: ParameterKind::Text { default: "." },
+}];
+
+pub struct OperationJsonQuery {}
+
+impl TextOperation for OperationJsonQuery {
+    fn get_id(&self) -> &'static str {
+        "JSON_QUERY"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "JSON Query"
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        QUERY_PARAMETERS
+    }
+
+    /// Queries starting with `$` are JSONPath and give an array of the matches, anything else
+    /// is a jq filter and gives each of its results on its own
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let value = parse_json(input)?;
+        let query = parameters.get_text("query").trim();
+
+        let results = match query.starts_with('$') {
+            true => {
+                let path =
+                    JsonPath::parse(query).map_err(|e| query_error(e.position(), e.message()))?;
+                vec![Value::Array(
+                    path.query(&value).all().into_iter().cloned().collect(),
+                )]
+            }
+            false => Filter::parse(query)?
+                .apply(&value)
+                .map_err(TextOperationError::InvalidInputError)?,
+        };
+
+        let mut output = String::new();
+        for result in results {
+            let written = serde_json::to_string_pretty(&result)
+                .map_err(|e| TextOperationError::InvalidInputError(e.to_string()))?;
+            output.push_str(&written);
+            output.push('\n');
+        }
+
+        Ok(TextOperationResult::with_string(output))
+    }
+}
+
+/// An error in the query, `position` counts characters from 1
+fn query_error(position: usize, message: &str) -> TextOperationError {
+    TextOperationError::InvalidInputError(format!(
+        "invalid query at position {}: {}",
+        position, message
+    ))
+}
+
+/// A jq filter, of which field access, indexes, slices, iteration, pipes, commas, array
+/// construction, comparisons, `and`, `or` and the functions `select`, `map`, `keys`, `length`
+/// and `not` are supported
+#[derive(Debug)]
+enum Filter {
+    Identity,
+    Literal(Value),
+    Field(Box<Filter>, String),
+    Index(Box<Filter>, i64),
+    Slice(Box<Filter>, Option<i64>, Option<i64>),
+    Iterate(Box<Filter>),
+    Collect(Option<Box<Filter>>),
+    Pipe(Box<Filter>, Box<Filter>),
+    Comma(Box<Filter>, Box<Filter>),
+    Compare(Box<Filter>, Comparison, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Select(Box<Filter>),
+    Map(Box<Filter>),
+    Keys,
+    Length,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+const COMPARISONS: [(&str, Comparison); 6] = [
+    ("==", Comparison::Equal),
+    ("!=", Comparison::NotEqual),
+    ("<=", Comparison::LessOrEqual),
+    (">=", Comparison::GreaterOrEqual),
+    ("<", Comparison::Less),
+    (">", Comparison::Greater),
+];
+
+impl Filter {
+    fn parse(query: &str) -> Result<Filter, TextOperationError> {
+        let mut parser = FilterParser { query, position: 0 };
+        let filter = parser.parse_pipe()?;
+        parser.skip_whitespace();
+        match parser.position < query.len() {
+            true => Err(parser.error("unexpected character")),
+            false => Ok(filter),
+        }
+    }
+
+    /// Runs the filter on `input`, a filter can give any number of results
+    fn apply(&self, input: &Value) -> Result<Vec<Value>, String> {
+        Ok(match self {
+            Filter::Identity => vec![input.clone()],
+            Filter::Literal(value) => vec![value.clone()],
+            Filter::Field(filter, name) => filter
+                .apply(input)?
+                .iter()
+                .map(|value| match value {
+                    Value::Object(object) => Ok(object.get(name).cloned().unwrap_or(Value::Null)),
+                    Value::Null => Ok(Value::Null),
+                    _ => Err(format!(
+                        "cannot index {} with \"{}\"",
+                        type_name(value),
+                        name
+                    )),
+                })
+                .collect::<Result<_, _>>()?,
+            Filter::Index(filter, index) => filter
+                .apply(input)?
+                .iter()
+                .map(|value| match value {
+                    Value::Array(array) => Ok(resolve_index(*index, array.len())
+                        .and_then(|index| array.get(index))
+                        .cloned()
+                        .unwrap_or(Value::Null)),
+                    Value::Null => Ok(Value::Null),
+                    _ => Err(format!("cannot index {} with a number", type_name(value))),
+                })
+                .collect::<Result<_, _>>()?,
+            Filter::Slice(filter, start, end) => filter
+                .apply(input)?
+                .iter()
+                .map(|value| match value {
+                    Value::Array(array) => {
+                        let (start, end) = slice_range(*start, *end, array.len());
+                        Ok(Value::Array(array[start..end].to_vec()))
+                    }
+                    Value::String(string) => {
+                        let chars: Vec<char> = string.chars().collect();
+                        let (start, end) = slice_range(*start, *end, chars.len());
+                        Ok(Value::String(chars[start..end].iter().collect()))
+                    }
+                    Value::Null => Ok(Value::Null),
+                    _ => Err(format!("cannot slice {}", type_name(value))),
+                })
+                .collect::<Result<_, _>>()?,
+            Filter::Iterate(filter) => {
+                let mut results = vec![];
+                for value in filter.apply(input)? {
+                    match value {
+                        Value::Array(array) => results.extend(array),
+                        Value::Object(object) => results.extend(object.into_iter().map(|(_, v)| v)),
+                        _ => return Err(format!("cannot iterate over {}", type_name(&value))),
+                    }
+                }
+                results
+            }
+            Filter::Collect(filter) => match filter {
+                Some(filter) => vec![Value::Array(filter.apply(input)?)],
+                None => vec![Value::Array(vec![])],
+            },
+            Filter::Pipe(first, second) => {
+                let mut results = vec![];
+                for value in first.apply(input)? {
+                    results.extend(second.apply(&value)?);
+                }
+                results
+            }
+            Filter::Comma(first, second) => {
+                let mut results = first.apply(input)?;
+                results.extend(second.apply(input)?);
+                results
+            }
+            Filter::Compare(left, comparison, right) => {
+                let mut results = vec![];
+                for right in right.apply(input)? {
+                    for left in left.apply(input)? {
+                        let order = compare_values(&left, &right);
+                        let result = match comparison {
+                            Comparison::Equal => order == Ordering::Equal,
+                            Comparison::NotEqual => order != Ordering::Equal,
+                            Comparison::Less => order == Ordering::Less,
+                            Comparison::LessOrEqual => order != Ordering::Greater,
+                            Comparison::Greater => order == Ordering::Greater,
+                            Comparison::GreaterOrEqual => order != Ordering::Less,
+                        };
+                        results.push(Value::Bool(result));
+                    }
+                }
+                results
+            }
+            Filter::And(left, right) => {
+                let mut results = vec![];
+                for left in left.apply(input)? {
+                    match is_truthy(&left) {
+                        true => results.extend(
+                            right
+                                .apply(input)?
+                                .iter()
+                                .map(|right| Value::Bool(is_truthy(right))),
+                        ),
+                        false => results.push(Value::Bool(false)),
+                    }
+                }
+                results
+            }
+            Filter::Or(left, right) => {
+                let mut results = vec![];
+                for left in left.apply(input)? {
+                    match is_truthy(&left) {
+                        true => results.push(Value::Bool(true)),
+                        false => results.extend(
+                            right
+                                .apply(input)?
+                                .iter()
+                                .map(|right| Value::Bool(is_truthy(right))),
+                        ),
+                    }
+                }
+                results
+            }
+            Filter::Select(condition) => match condition.apply(input)?.iter().any(is_truthy) {
+                true => vec![input.clone()],
+                false => vec![],
+            },
+            Filter::Map(filter) => {
+                let mut mapped = vec![];
+                for element in Filter::Iterate(Box::new(Filter::Identity)).apply(input)? {
+                    mapped.extend(filter.apply(&element)?);
+                }
+                vec![Value::Array(mapped)]
+            }
+            Filter::Keys => match input {
+                Value::Object(object) => {
+                    let mut keys: Vec<&String> = object.keys().collect();
+                    keys.sort();
+                    vec![Value::Array(
+                        keys.into_iter().cloned().map(Value::String).collect(),
+                    )]
+                }
+                Value::Array(array) => {
+                    vec![Value::Array((0..array.len()).map(Value::from).collect())]
+                }
+                _ => return Err(format!("{} has no keys", type_name(input))),
+            },
+            Filter::Length => vec![match input {
+                Value::Null => Value::from(0),
+                Value::Number(number) => number_value(number.as_f64().unwrap_or(0.).abs()),
+                Value::String(string) => Value::from(string.chars().count()),
+                Value::Array(array) => Value::from(array.len()),
+                Value::Object(object) => Value::from(object.len()),
+                Value::Bool(_) => return Err(String::from("boolean has no length")),
+            }],
+            Filter::Not => vec![Value::Bool(!is_truthy(input))],
+        })
+    }
+}
+
+/// An index from the end of the array when negative, `None` when it is before the start
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    match index < 0 {
+        true => (len as i64 + index).try_into().ok(),
+        false => Some(index as usize),
+    }
+}
+
+/// The bounds of a slice clamped to the length, negative bounds count from the end
+fn slice_range(start: Option<i64>, end: Option<i64>, len: usize) -> (usize, usize) {
+    let bound = |bound: i64| match bound < 0 {
+        true => (len as i64 + bound).max(0) as usize,
+        false => (bound as usize).min(len),
+    };
+    let start = start.map_or(0, bound);
+    let end = end.map_or(len, bound);
+    (start, end.max(start))
+}
+
+fn number_value(number: f64) -> Value {
+    match number.fract() == 0. && number.abs() < i64::MAX as f64 {
+        true => Value::from(number as i64),
+        false => Number::from_f64(number).map_or(Value::Null, Value::Number),
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Orders values the way jq does, first by type and then by value
+fn compare_values(left: &Value, right: &Value) -> Ordering {
+    let rank = |value: &Value| match value {
+        Value::Null => 0,
+        Value::Bool(false) => 1,
+        Value::Bool(true) => 2,
+        Value::Number(_) => 3,
+        Value::String(_) => 4,
+        Value::Array(_) => 5,
+        Value::Object(_) => 6,
+    };
+
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => {
+            let left = left.as_f64().unwrap_or(f64::NAN);
+            let right = right.as_f64().unwrap_or(f64::NAN);
+            left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+        }
+        (Value::String(left), Value::String(right)) => left.cmp(right),
+        (Value::Array(left), Value::Array(right)) => left
+            .iter()
+            .zip(right)
+            .map(|(left, right)| compare_values(left, right))
+            .find(|order| order.is_ne())
+            .unwrap_or(left.len().cmp(&right.len())),
+        (Value::Object(left), Value::Object(right)) => {
+            let mut left_keys: Vec<&String> = left.keys().collect();
+            let mut right_keys: Vec<&String> = right.keys().collect();
+            left_keys.sort();
+            right_keys.sort();
+            left_keys.cmp(&right_keys).then_with(|| {
+                left_keys
+                    .iter()
+                    .map(|key| compare_values(&left[*key], &right[*key]))
+                    .find(|order| order.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+        }
+        _ => rank(left).cmp(&rank(right)),
+    }
+}
+
+struct FilterParser<'a> {
+    query: &'a str,
+    position: usize,
+}
+
+impl<'a> FilterParser<'a> {
+    fn error(&self, message: &str) -> TextOperationError {
+        let position = self.query[..self.position].chars().count() + 1;
+        query_error(position, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.query[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.query[self.position..].chars().next()
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        match self.query[self.position..].starts_with(token) {
+            true => {
+                self.position += token.len();
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), TextOperationError> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected `{}`", token))),
+        }
+    }
+
+    fn parse_pipe(&mut self) -> Result<Filter, TextOperationError> {
+        let mut filter = self.parse_comma()?;
+        while self.eat("|") {
+            filter = Filter::Pipe(Box::new(filter), Box::new(self.parse_comma()?));
+        }
+        Ok(filter)
+    }
+
+    fn parse_comma(&mut self) -> Result<Filter, TextOperationError> {
+        let mut filter = self.parse_or()?;
+        while self.eat(",") {
+            filter = Filter::Comma(Box::new(filter), Box::new(self.parse_or()?));
+        }
+        Ok(filter)
+    }
+
+    fn parse_or(&mut self) -> Result<Filter, TextOperationError> {
+        let mut filter = self.parse_and()?;
+        while self.eat_keyword("or") {
+            filter = Filter::Or(Box::new(filter), Box::new(self.parse_and()?));
+        }
+        Ok(filter)
+    }
+
+    fn parse_and(&mut self) -> Result<Filter, TextOperationError> {
+        let mut filter = self.parse_comparison()?;
+        while self.eat_keyword("and") {
+            filter = Filter::And(Box::new(filter), Box::new(self.parse_comparison()?));
+        }
+        Ok(filter)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Filter, TextOperationError> {
+        let filter = self.parse_postfix()?;
+        for (token, comparison) in COMPARISONS {
+            if self.eat(token) {
+                let right = self.parse_postfix()?;
+                return Ok(Filter::Compare(
+                    Box::new(filter),
+                    comparison,
+                    Box::new(right),
+                ));
+            }
+        }
+        Ok(filter)
+    }
+
+    /// A term followed by any number of `.field`, `[index]`, `[start:end]` and `[]`
+    fn parse_postfix(&mut self) -> Result<Filter, TextOperationError> {
+        let mut filter = match self.peek() {
+            Some('.') => {
+                self.position += 1;
+                match self.query[self.position..].chars().next() {
+                    Some('[') => Filter::Identity,
+                    Some(c) if c == '"' || c == '_' || c.is_alphabetic() => {
+                        let name = self.parse_name()?;
+                        Filter::Field(Box::new(Filter::Identity), name)
+                    }
+                    _ => Filter::Identity,
+                }
+            }
+            _ => self.parse_term()?,
+        };
+
+        loop {
+            let rest = &self.query[self.position..];
+            if rest.starts_with('.') && !rest.starts_with("..") {
+                self.position += 1;
+                if self.query[self.position..].starts_with('[') {
+                    continue;
+                }
+                let name = self.parse_name()?;
+                filter = Filter::Field(Box::new(filter), name);
+            } else if rest.starts_with('[') {
+                self.position += 1;
+                filter = self.parse_brackets(filter)?;
+            } else {
+                return Ok(filter);
+            }
+        }
+    }
+
+    /// What follows an opening bracket, which has already been read
+    fn parse_brackets(&mut self, filter: Filter) -> Result<Filter, TextOperationError> {
+        if self.eat("]") {
+            return Ok(Filter::Iterate(Box::new(filter)));
+        }
+        if self.peek() == Some('"') {
+            let name = self.parse_string()?;
+            self.expect("]")?;
+            return Ok(Filter::Field(Box::new(filter), name));
+        }
+
+        let start = self.parse_integer()?;
+        if self.eat(":") {
+            let end = self.parse_integer()?;
+            self.expect("]")?;
+            return Ok(Filter::Slice(Box::new(filter), start, end));
+        }
+        self.expect("]")?;
+        match start {
+            Some(index) => Ok(Filter::Index(Box::new(filter), index)),
+            None => Err(self.error("expected an index")),
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Filter, TextOperationError> {
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let filter = self.parse_pipe()?;
+                self.expect(")")?;
+                Ok(filter)
+            }
+            Some('[') => {
+                self.position += 1;
+                if self.eat("]") {
+                    return Ok(Filter::Collect(None));
+                }
+                let filter = self.parse_pipe()?;
+                self.expect("]")?;
+                Ok(Filter::Collect(Some(Box::new(filter))))
+            }
+            Some('"') => Ok(Filter::Literal(Value::String(self.parse_string()?))),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let rest = &self.query[self.position..];
+                let length = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || "-+.".contains(c)))
+                    .unwrap_or(rest.len());
+                let number = serde_json::from_str::<Number>(&rest[..length])
+                    .map_err(|_| self.error("invalid number"))?;
+                self.position += length;
+                Ok(Filter::Literal(Value::Number(number)))
+            }
+            Some(c) if c.is_alphabetic() => {
+                let start = self.position;
+                let name = self.parse_identifier();
+                match name {
+                    "true" => Ok(Filter::Literal(Value::Bool(true))),
+                    "false" => Ok(Filter::Literal(Value::Bool(false))),
+                    "null" => Ok(Filter::Literal(Value::Null)),
+                    "keys" => Ok(Filter::Keys),
+                    "length" => Ok(Filter::Length),
+                    "not" => Ok(Filter::Not),
+                    "select" | "map" => {
+                        self.expect("(")?;
+                        let argument = Box::new(self.parse_pipe()?);
+                        self.expect(")")?;
+                        match name {
+                            "select" => Ok(Filter::Select(argument)),
+                            _ => Ok(Filter::Map(argument)),
+                        }
+                    }
+                    _ => {
+                        self.position = start;
+                        Err(self.error(&format!("unknown function {}", name)))
+                    }
+                }
+            }
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of query")),
+        }
+    }
+
+    fn parse_identifier(&mut self) -> &'a str {
+        let rest = &self.query[self.position..];
+        let length = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespace();
+        let start = self.position;
+        match self.parse_identifier() == keyword {
+            true => true,
+            false => {
+                self.position = start;
+                false
+            }
+        }
+    }
+
+    /// A field name after a dot, either an identifier or a quoted string
+    fn parse_name(&mut self) -> Result<String, TextOperationError> {
+        if self.query[self.position..].starts_with('"') {
+            return self.parse_string();
+        }
+        match self.parse_identifier() {
+            "" => Err(self.error("expected a field name")),
+            name => Ok(String::from(name)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, TextOperationError> {
+        self.skip_whitespace();
+        let rest = &self.query[self.position..];
+        let mut escaped = false;
+        let end = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| {
+                let closes = !escaped && *c == '"';
+                escaped = !escaped && *c == '\\';
+                closes
+            })
+            .map(|(index, _)| index + 1)
+            .ok_or_else(|| self.error("unterminated string"))?;
+
+        let string =
+            serde_json::from_str(&rest[..end]).map_err(|_| self.error("invalid string"))?;
+        self.position += end;
+        Ok(string)
+    }
+
+    fn parse_integer(&mut self) -> Result<Option<i64>, TextOperationError> {
+        self.skip_whitespace();
+        let rest = &self.query[self.position..];
+        let length = rest
+            .char_indices()
+            .find(|(index, c)| !(c.is_ascii_digit() || (*index == 0 && *c == '-')))
+            .map_or(rest.len(), |(index, _)| index);
+        if length == 0 {
+            return Ok(None);
+        }
+
+        let integer = rest[..length]
+            .parse()
+            .map_err(|_| self.error("invalid index"))?;
+        self.position += length;
+        Ok(Some(integer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    const USERS: &str = r#"{
+        "users": [
+            {"name": "ada", "age": 36, "roles": ["admin"]},
+            {"name": "linus", "age": 21, "roles": []},
+            {"name": "grace", "age": 85, "roles": ["admin", "dev"]}
+        ]
+    }"#;
+
+    fn query(query: &str) -> Result<String, TextOperationError> {
+        let operation = OperationJsonQuery {};
+        let parameters = operation
+            .default_parameters()
+            .with("query", ParameterValue::Text(String::from(query)));
+        operation
+            .convert(USERS, &parameters)
+            .map(|result| result.text_value.unwrap())
+    }
+
+    #[test]
+    fn it_queries_with_jsonpath() {
+        assert_eq!(
+            query("$.users[?@.age > 30].name").unwrap(),
+            "[\n  \"ada\",\n  \"grace\"\n]\n"
+        );
+    }
+
+    #[test]
+    fn it_accesses_fields_and_iterates_with_jq() {
+        assert_eq!(query(".users[0].name").unwrap(), "\"ada\"\n");
+        assert_eq!(
+            query(".users[].name").unwrap(),
+            "\"ada\"\n\"linus\"\n\"grace\"\n"
+        );
+        assert_eq!(query(".users[-1].roles[1]").unwrap(), "\"dev\"\n");
+        assert_eq!(query(".users[1:] | length").unwrap(), "2\n");
+        assert_eq!(query(".missing.field").unwrap(), "null\n");
+    }
+
+    #[test]
+    fn it_selects_and_maps_with_jq() {
+        assert_eq!(
+            query(r#"[.users[] | select(.age > 30 and (.roles | length) > 1) | .name]"#).unwrap(),
+            "[\n  \"grace\"\n]\n"
+        );
+        assert_eq!(
+            query(".users | map(.age)").unwrap(),
+            "[\n  36,\n  21,\n  85\n]\n"
+        );
+        assert_eq!(
+            query(r#".users[0] | keys, (.name == "ada")"#).unwrap(),
+            "[\n  \"age\",\n  \"name\",\n  \"roles\"\n]\ntrue\n"
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_queries() {
+        assert!(query(".users[").is_err());
+        assert!(query(".users | sort").is_err());
+        assert!(query("$.users[").is_err());
+        assert!(query(".users.name").is_err());
+    }
+}
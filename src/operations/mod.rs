@@ -1,7 +1,21 @@
+mod alphabets;
+mod base_encodings;
+mod charset;
+mod cipher;
+mod compression;
+mod data_format;
+mod escape;
 mod hex;
+mod hex_dump;
+mod json;
+mod json_query;
 mod none;
+mod radix;
 mod text;
 mod text_operation;
+mod unicode;
 mod web;
 
+pub mod magic;
+pub mod pipeline;
 pub mod text_operations;
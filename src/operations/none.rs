@@ -1,4 +1,6 @@
-use super::text_operation::{TextOperation, TextOperationError, TextOperationResult};
+use super::text_operation::{
+    OperationParameters, StreamConverter, TextOperation, TextOperationError, TextOperationResult,
+};
 
 pub struct OperationNone {}
 
@@ -11,7 +13,36 @@ impl TextOperation for OperationNone {
         "None"
     }
 
-    fn convert(&self, input: &str) -> Result<TextOperationResult, TextOperationError> {
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
         Ok(TextOperationResult::with_string(String::from(input)))
     }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(TextOperationResult::with_bytes(input.to_vec()))
+    }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(PassThrough {}))
+    }
+}
+
+struct PassThrough {}
+
+impl StreamConverter for PassThrough {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        output.extend_from_slice(input);
+        Ok(())
+    }
+
+    fn finish(&mut self, _output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        Ok(())
+    }
 }
@@ -0,0 +1,465 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::{self, Read, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use super::text_operation::{
+    DataType, OperationParameters, StreamConverter, TextOperation, TextOperationError,
+    TextOperationResult,
+};
+use super::text_operations::TextOperations;
+use crate::fl;
+use serde::{Deserialize, Serialize};
+
+/// An operation in a pipeline along with the parameters selected for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineStep {
+    pub operation_id: String,
+    #[serde(default)]
+    pub parameters: OperationParameters,
+}
+
+impl PipelineStep {
+    pub fn new(operation_id: &str, parameters: OperationParameters) -> Self {
+        PipelineStep {
+            operation_id: String::from(operation_id),
+            parameters,
+        }
+    }
+}
+
+/// A failed pipeline run, identifying the step that failed and keeping the output of the
+/// last step that succeeded
+#[derive(Debug)]
+pub struct PipelineError {
+    pub step: usize,
+    pub operation_id: String,
+    pub error: TextOperationError,
+    pub last_output: TextOperationResult,
+}
+
+impl Display for PipelineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operation = TextOperations::get_instance().get_operation_or_noop(&self.operation_id);
+        let message = fl!(
+            "error-in-step",
+            step = (self.step + 1).to_string(),
+            operation = operation.get_name(),
+            message = self.error.to_string()
+        );
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for PipelineError {}
+
+/// The number of characters of each intermediate output kept for previews
+pub const STEP_PREVIEW_LENGTH: usize = 2048;
+
+/// What a single step produced during a run, used to inspect intermediate results
+#[derive(Debug, Clone)]
+pub struct StepOutput {
+    pub operation_id: String,
+    pub output_type: DataType,
+    /// Size of the output in bytes
+    pub size: usize,
+    pub duration: Duration,
+    /// The start of the output rendered for display, bytes that are not UTF-8 are shown as hex
+    pub preview: String,
+}
+
+/// Runs the input through each step in order. Bytes produced by a step are handed to the
+/// next step as bytes so binary data is never rendered to text part way through a chain.
+pub fn run_operations(
+    input: TextOperationResult,
+    steps: &[PipelineStep],
+) -> Result<TextOperationResult, PipelineError> {
+    execute(input, steps, None, None).expect("runs without a cancel flag always finish")
+}
+
+/// Runs the pipeline like `run_operations` while also recording the output of every step that
+/// succeeded
+pub fn run_operations_traced(
+    input: TextOperationResult,
+    steps: &[PipelineStep],
+) -> (Result<TextOperationResult, PipelineError>, Vec<StepOutput>) {
+    let mut step_outputs = vec![];
+    let result = execute(input, steps, Some(&mut step_outputs), None)
+        .expect("runs without a cancel flag always finish");
+    (result, step_outputs)
+}
+
+/// Runs the pipeline like `run_operations_traced` but checks `cancelled` before every step,
+/// returning `None` once it has been set so a superseded run stops as early as possible
+pub fn run_operations_cancellable(
+    input: TextOperationResult,
+    steps: &[PipelineStep],
+    cancelled: &AtomicBool,
+) -> Option<(Result<TextOperationResult, PipelineError>, Vec<StepOutput>)> {
+    let mut step_outputs = vec![];
+    let result = execute(input, steps, Some(&mut step_outputs), Some(cancelled))?;
+    Some((result, step_outputs))
+}
+
+fn execute(
+    input: TextOperationResult,
+    steps: &[PipelineStep],
+    mut step_outputs: Option<&mut Vec<StepOutput>>,
+    cancelled: Option<&AtomicBool>,
+) -> Option<Result<TextOperationResult, PipelineError>> {
+    let text_operations = TextOperations::get_instance();
+    let mut value = input;
+
+    for (index, step) in steps.iter().enumerate() {
+        if cancelled.is_some_and(|cancelled| cancelled.load(Ordering::Relaxed)) {
+            return None;
+        }
+
+        let operation = text_operations.get_operation_or_noop(step.operation_id.as_str());
+        let parameters = operation.default_parameters().merged(&step.parameters);
+        let started = Instant::now();
+        value = match apply_operation(operation.as_ref(), &value, &parameters) {
+            Ok(result) => result,
+            Err(error) => {
+                return Some(Err(PipelineError {
+                    step: index,
+                    operation_id: step.operation_id.clone(),
+                    error,
+                    last_output: value,
+                }))
+            }
+        };
+
+        if let Some(step_outputs) = step_outputs.as_mut() {
+            step_outputs.push(StepOutput {
+                operation_id: step.operation_id.clone(),
+                output_type: value.get_type(),
+                size: value.get_size(),
+                duration: started.elapsed(),
+                preview: value.render_preview(STEP_PREVIEW_LENGTH),
+            });
+        }
+    }
+
+    Some(Ok(value))
+}
+
+/// How much input is read at a time when streaming
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A failed streaming run, either reading or writing failed or one of the steps did
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Step(PipelineError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Step(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+/// A pipeline where every step converts its input in chunks
+pub struct PipelineStream {
+    operation_ids: Vec<String>,
+    converters: Vec<Box<dyn StreamConverter>>,
+}
+
+impl PipelineStream {
+    /// Creates the stream for the steps, or `None` when one of them cannot stream
+    pub fn new(steps: &[PipelineStep]) -> Option<Self> {
+        let text_operations = TextOperations::get_instance();
+        let mut converters: Vec<Box<dyn StreamConverter>> = vec![];
+
+        for step in steps {
+            let operation = text_operations.get_operation_or_noop(step.operation_id.as_str());
+            let parameters = operation.default_parameters().merged(&step.parameters);
+            converters.push(operation.get_stream(&parameters)?);
+        }
+
+        Some(PipelineStream {
+            operation_ids: steps.iter().map(|step| step.operation_id.clone()).collect(),
+            converters,
+        })
+    }
+
+    /// Runs `input` through the steps a chunk at a time, writing the result to `output` as it
+    /// is produced so inputs larger than memory can be converted
+    pub fn run(mut self, input: &mut dyn Read, output: &mut dyn Write) -> Result<(), StreamError> {
+        let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+
+        loop {
+            let length = match input.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(length) => length,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(StreamError::Io(e)),
+            };
+
+            let mut value = chunk[..length].to_vec();
+            for index in 0..self.converters.len() {
+                let mut converted = Vec::with_capacity(value.len());
+                self.converters[index]
+                    .update(&value, &mut converted)
+                    .map_err(|e| self.step_error(index, e))?;
+                value = converted;
+            }
+            output.write_all(&value).map_err(StreamError::Io)?;
+        }
+
+        // anything a step held back still has to go through the steps after it
+        let mut value: Vec<u8> = vec![];
+        for index in 0..self.converters.len() {
+            let mut converted = vec![];
+            let converter = &mut self.converters[index];
+            converter
+                .update(&value, &mut converted)
+                .and_then(|_| converter.finish(&mut converted))
+                .map_err(|e| self.step_error(index, e))?;
+            value = converted;
+        }
+        output.write_all(&value).map_err(StreamError::Io)?;
+        output.flush().map_err(StreamError::Io)
+    }
+
+    fn step_error(&self, step: usize, error: TextOperationError) -> StreamError {
+        StreamError::Step(PipelineError {
+            step,
+            operation_id: self.operation_ids[step].clone(),
+            error,
+            last_output: TextOperationResult::with_bytes(vec![]),
+        })
+    }
+}
+
+/// Builds the chain that undoes `steps`, the inverse of each step in reverse order. Steps that
+/// do nothing are dropped and parameters are kept where the inverse has a parameter with the
+/// same id. Returns `None` when any of the steps cannot be inverted.
+pub fn reverse_steps(steps: &[PipelineStep]) -> Option<Vec<PipelineStep>> {
+    let text_operations = TextOperations::get_instance();
+    let noop_id = text_operations.get_noop().get_id();
+    let mut reversed = vec![];
+
+    for step in steps.iter().rev() {
+        if step.operation_id == noop_id {
+            continue;
+        }
+
+        let operation = text_operations.get_operation(step.operation_id.as_str())?;
+        let inverse_id = operation.get_inverse()?;
+        let inverse = text_operations.get_operation(inverse_id.as_str())?;
+
+        let mut parameters = OperationParameters::default();
+        for parameter in inverse.get_parameters() {
+            if let Some(value) = step.parameters.get(parameter.id) {
+                parameters.set(parameter.id, value.clone());
+            }
+        }
+
+        reversed.push(PipelineStep::new(inverse.get_id(), parameters));
+    }
+
+    Some(reversed)
+}
+
+/// Applies a single operation, routing the value to `convert` or `convert_bytes` depending on
+/// what the previous step produced and what the operation consumes
+pub fn apply_operation(
+    operation: &dyn TextOperation,
+    input: &TextOperationResult,
+    parameters: &OperationParameters,
+) -> Result<TextOperationResult, TextOperationError> {
+    match (&input.byte_value, &input.text_value) {
+        (Some(bytes), _) => operation.convert_bytes(bytes, parameters),
+        (None, Some(text)) => match operation.get_input_type() {
+            DataType::Bytes => operation.convert_bytes(text.as_bytes(), parameters),
+            DataType::Text => operation.convert(text, parameters),
+        },
+        (None, None) => operation.convert("", parameters),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    fn steps(ids: &[&str]) -> Vec<PipelineStep> {
+        ids.iter()
+            .map(|id| PipelineStep::new(id, OperationParameters::default()))
+            .collect()
+    }
+
+    #[test]
+    fn it_passes_bytes_between_steps() {
+        let input = TextOperationResult::with_string(String::from("0a00ff"));
+
+        let result = run_operations(input, &steps(&["FROM_HEX", "TO_BASE64"]));
+        assert_eq!(result.unwrap().text_value.unwrap(), "CgD/");
+    }
+
+    #[test]
+    fn it_decodes_bytes_as_utf8_for_text_operations() {
+        let input = TextOperationResult::with_string(String::from("616263"));
+
+        let result = run_operations(input, &steps(&["FROM_HEX", "UPPER_CASE"]));
+        assert_eq!(result.unwrap().text_value.unwrap(), "ABC");
+    }
+
+    #[test]
+    fn it_keeps_binary_output_as_bytes() {
+        let input = TextOperationResult::with_string(String::from("CgD/"));
+
+        let result = run_operations(input, &steps(&["FROM_BASE64", "NONE"]));
+        assert_eq!(result.unwrap().byte_value.unwrap(), vec![0x0a, 0x00, 0xff]);
+    }
+
+    #[test]
+    fn it_reports_the_failing_step_and_keeps_the_last_output() {
+        let input = TextOperationResult::with_string(String::from("ab-c"));
+
+        let result = run_operations(input, &steps(&["UPPER_CASE", "FROM_HEX", "NONE"]));
+        let error = result.unwrap_err();
+        assert_eq!(error.step, 1);
+        assert_eq!(error.operation_id, "FROM_HEX");
+        assert_eq!(error.error.get_range(), Some(2..3));
+        assert_eq!(error.last_output.text_value.unwrap(), "AB-C");
+    }
+
+    #[test]
+    fn it_reverses_a_chain_of_invertible_steps() {
+        let reversed = reverse_steps(&steps(&["TO_HEX", "NONE", "TO_BASE64", "NONE"])).unwrap();
+        let reversed_ids: Vec<&str> = reversed
+            .iter()
+            .map(|step| step.operation_id.as_str())
+            .collect();
+        assert_eq!(reversed_ids, vec!["FROM_BASE64", "FROM_HEX"]);
+
+        let input = TextOperationResult::with_string(String::from("round trip"));
+        let encoded = run_operations(input, &steps(&["TO_HEX", "TO_BASE64"])).unwrap();
+        let decoded = run_operations(encoded, &reversed).unwrap();
+        assert_eq!(decoded.byte_value.unwrap(), b"round trip");
+    }
+
+    #[test]
+    fn it_does_not_reverse_a_chain_with_a_non_invertible_step() {
+        let reversed = reverse_steps(&steps(&["TO_HEX", "UPPER_CASE"]));
+        assert!(reversed.is_none());
+    }
+
+    #[test]
+    fn it_records_the_output_of_each_step() {
+        let input = TextOperationResult::with_string(String::from("CgD/"));
+
+        let (result, step_outputs) =
+            run_operations_traced(input, &steps(&["FROM_BASE64", "TO_HEX", "FROM_HEX"]));
+        assert!(result.is_ok());
+        assert_eq!(step_outputs.len(), 3);
+        assert_eq!(step_outputs[0].output_type, DataType::Bytes);
+        assert_eq!(step_outputs[0].size, 3);
+        assert_eq!(step_outputs[0].preview, "0a00ff");
+        assert_eq!(step_outputs[1].output_type, DataType::Text);
+        assert_eq!(step_outputs[1].operation_id, "TO_HEX");
+    }
+
+    #[test]
+    fn it_stops_recording_at_the_failing_step() {
+        let input = TextOperationResult::with_string(String::from("4g"));
+
+        let (result, step_outputs) = run_operations_traced(input, &steps(&["NONE", "FROM_HEX"]));
+        assert_eq!(result.unwrap_err().step, 1);
+        assert_eq!(step_outputs.len(), 1);
+    }
+
+    #[test]
+    fn it_renders_invalid_utf8_bytes_as_padded_hex() {
+        let rendered: String = TextOperationResult::with_bytes(vec![0x0a, 0x00, 0xff]).into();
+        assert_eq!(rendered, "0a00ff");
+    }
+
+    #[test]
+    fn it_applies_step_parameters_over_defaults() {
+        let input = TextOperationResult::with_string(String::from("AB"));
+        let parameters = OperationParameters::default()
+            .with("separator", ParameterValue::Text(String::from(":")));
+
+        let result = run_operations(input, &[PipelineStep::new("TO_HEX", parameters)]);
+        assert_eq!(result.unwrap().text_value.unwrap(), "41:42");
+    }
+
+    #[test]
+    fn it_stops_a_cancelled_run() {
+        let input = || TextOperationResult::with_string(String::from("AB"));
+        let steps = steps(&["TO_HEX", "TO_BASE64"]);
+
+        assert!(run_operations_cancellable(input(), &steps, &AtomicBool::new(false)).is_some());
+        assert!(run_operations_cancellable(input(), &steps, &AtomicBool::new(true)).is_none());
+    }
+
+    /// Hands out the input a few bytes at a time to exercise chunk boundaries
+    struct TrickleReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl Read for TrickleReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let length = self.data.len().min(buf.len()).min(3);
+            buf[..length].copy_from_slice(&self.data[..length]);
+            self.data = &self.data[length..];
+            Ok(length)
+        }
+    }
+
+    #[test]
+    fn it_streams_the_same_result_as_running_in_memory() {
+        let chain = steps(&[
+            "UPPER_CASE",
+            "TO_HEX",
+            "TO_BASE64",
+            "FROM_BASE64",
+            "FROM_HEX",
+        ]);
+        let input = "streaming ünïcödé text";
+        let mut output: Vec<u8> = vec![];
+
+        let stream = PipelineStream::new(&chain).unwrap();
+        let mut reader = TrickleReader {
+            data: input.as_bytes(),
+        };
+        assert!(stream.run(&mut reader, &mut output).is_ok());
+
+        let expected = run_operations(
+            TextOperationResult::with_string(String::from(input)),
+            &chain,
+        );
+        assert_eq!(output, expected.unwrap().byte_value.unwrap());
+    }
+
+    #[test]
+    fn it_only_streams_when_every_step_supports_it() {
+        assert!(PipelineStream::new(&steps(&["TO_HEX", "GZIP"])).is_none());
+    }
+
+    #[test]
+    fn it_reports_the_failing_step_when_streaming() {
+        let mut output: Vec<u8> = vec![];
+
+        let stream = PipelineStream::new(&steps(&["NONE", "FROM_HEX"])).unwrap();
+        match stream.run(&mut &b"41 4g"[..], &mut output) {
+            Err(StreamError::Step(e)) => {
+                assert_eq!(e.step, 1);
+                assert_eq!(e.error.get_range(), Some(4..5));
+            }
+            _ => panic!("expected the second step to fail"),
+        }
+    }
+}
@@ -0,0 +1,484 @@
+use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, TextOperation,
+    TextOperationError, TextOperationResult,
+};
+
+const WORD_SIZE_8: &str = "8-bit";
+const WORD_SIZE_16: &str = "16-bit";
+const WORD_SIZE_32: &str = "32-bit";
+const WORD_SIZE_64: &str = "64-bit";
+
+const BYTE_ORDER_BIG_ENDIAN: &str = "Big endian";
+const BYTE_ORDER_LITTLE_ENDIAN: &str = "Little endian";
+
+const RADIX_PARAMETERS: &[OperationParameter] = &[
+    OperationParameter {
+        id: "separator",
+        name: "Separator",
+        kind: ParameterKind::Text { default: " " },
+    },
+    OperationParameter {
+        id: "word_size",
+        name: "Word size",
+        kind: ParameterKind::Choice {
+            options: &[WORD_SIZE_8, WORD_SIZE_16, WORD_SIZE_32, WORD_SIZE_64],
+            default: WORD_SIZE_8,
+        },
+    },
+    OperationParameter {
+        id: "byte_order",
+        name: "Byte order",
+        kind: ParameterKind::Choice {
+            options: &[BYTE_ORDER_BIG_ENDIAN, BYTE_ORDER_LITTLE_ENDIAN],
+            default: BYTE_ORDER_BIG_ENDIAN,
+        },
+    },
+];
+
+/// How words are written in a base, binary and octal are padded to the width of the word
+struct Radix {
+    radix: u32,
+    padded: bool,
+}
+
+const BINARY: Radix = Radix {
+    radix: 2,
+    padded: true,
+};
+const OCTAL: Radix = Radix {
+    radix: 8,
+    padded: true,
+};
+const DECIMAL: Radix = Radix {
+    radix: 10,
+    padded: false,
+};
+
+impl Radix {
+    /// The number of digits in a padded word of `size` bytes
+    fn width(&self, size: usize) -> usize {
+        let bits = size * 8;
+        match self.radix {
+            2 => bits,
+            8 => bits.div_ceil(3),
+            _ => 0,
+        }
+    }
+
+    fn format(&self, value: u64, size: usize) -> String {
+        let width = match self.padded {
+            true => self.width(size),
+            false => 0,
+        };
+        match self.radix {
+            2 => format!("{:0width$b}", value),
+            8 => format!("{:0width$o}", value),
+            _ => format!("{:0width$}", value),
+        }
+    }
+}
+
+/// The word size in bytes
+fn word_size(parameters: &OperationParameters) -> usize {
+    match parameters.get_text("word_size") {
+        WORD_SIZE_16 => 2,
+        WORD_SIZE_32 => 4,
+        WORD_SIZE_64 => 8,
+        _ => 1,
+    }
+}
+
+fn encode_words(
+    input: &[u8],
+    radix: &Radix,
+    parameters: &OperationParameters,
+) -> Result<TextOperationResult, TextOperationError> {
+    let size = word_size(parameters);
+    let little_endian = parameters.get_text("byte_order") == BYTE_ORDER_LITTLE_ENDIAN;
+
+    if input.len() % size != 0 {
+        return Err(TextOperationError::InvalidInputInRange(
+            input.len() - input.len() % size..input.len(),
+            format!("incomplete {}-bit word", size * 8),
+        ));
+    }
+
+    let words: Vec<String> = input
+        .chunks(size)
+        .map(|word| {
+            let value = match little_endian {
+                true => word
+                    .iter()
+                    .rev()
+                    .fold(0u64, |value, b| value << 8 | *b as u64),
+                false => word.iter().fold(0u64, |value, b| value << 8 | *b as u64),
+            };
+            radix.format(value, size)
+        })
+        .collect();
+
+    Ok(TextOperationResult::with_string(
+        words.join(parameters.get_text("separator")),
+    ))
+}
+
+/// Parses words separated by whitespace or the characters of the separator. Padded words
+/// written without a separator are split at the width of the word.
+fn decode_words(
+    input: &str,
+    radix: &Radix,
+    parameters: &OperationParameters,
+) -> Result<TextOperationResult, TextOperationError> {
+    let size = word_size(parameters);
+    let little_endian = parameters.get_text("byte_order") == BYTE_ORDER_LITTLE_ENDIAN;
+    let separator = parameters.get_text("separator");
+    let width = radix.width(size);
+    let mut bytes: Vec<u8> = vec![];
+
+    let tokens = input
+        .split(|c: char| c.is_whitespace() || separator.contains(c))
+        .filter(|token| !token.is_empty());
+    for token in tokens {
+        let token_start = token.as_ptr() as usize - input.as_ptr() as usize;
+        // checking first keeps the split below on character boundaries and stops a sign
+        // being read as part of a value
+        if let Some(offset) = token.find(|c: char| !c.is_digit(radix.radix)) {
+            return Err(TextOperationError::InvalidInputAtOffset(
+                token_start + offset,
+            ));
+        }
+        let words: Vec<(usize, &str)> = match radix.padded && token.len() > width {
+            true => (0..token.len())
+                .step_by(width)
+                .map(|start| (start, &token[start..(start + width).min(token.len())]))
+                .collect(),
+            false => vec![(0, token)],
+        };
+
+        for (start, word) in words {
+            let range = token_start + start..token_start + start + word.len();
+            let value = u64::from_str_radix(word, radix.radix).map_err(|e| {
+                TextOperationError::InvalidInputInRange(range.clone(), e.to_string())
+            })?;
+            if size < 8 && value >> (size * 8) != 0 {
+                return Err(TextOperationError::InvalidInputInRange(
+                    range,
+                    format!("value does not fit in {} bits", size * 8),
+                ));
+            }
+
+            let word_bytes = &value.to_be_bytes()[8 - size..];
+            match little_endian {
+                true => bytes.extend(word_bytes.iter().rev()),
+                false => bytes.extend_from_slice(word_bytes),
+            }
+        }
+    }
+
+    Ok(TextOperationResult::with_bytes(bytes))
+}
+
+pub struct OperationToBinary {}
+
+impl TextOperation for OperationToBinary {
+    fn get_id(&self) -> &'static str {
+        "TO_BINARY"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "UTF-8 to Binary"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_BINARY"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        RADIX_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        encode_words(input, &BINARY, parameters)
+    }
+}
+
+pub struct OperationFromBinary {}
+
+impl TextOperation for OperationFromBinary {
+    fn get_id(&self) -> &'static str {
+        "FROM_BINARY"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Binary to UTF-8"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_BINARY"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        RADIX_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        decode_words(input, &BINARY, parameters)
+    }
+}
+
+pub struct OperationToOctal {}
+
+impl TextOperation for OperationToOctal {
+    fn get_id(&self) -> &'static str {
+        "TO_OCTAL"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "UTF-8 to Octal"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_OCTAL"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        RADIX_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        encode_words(input, &OCTAL, parameters)
+    }
+}
+
+pub struct OperationFromOctal {}
+
+impl TextOperation for OperationFromOctal {
+    fn get_id(&self) -> &'static str {
+        "FROM_OCTAL"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Octal to UTF-8"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_OCTAL"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        RADIX_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        decode_words(input, &OCTAL, parameters)
+    }
+}
+
+pub struct OperationToDecimal {}
+
+impl TextOperation for OperationToDecimal {
+    fn get_id(&self) -> &'static str {
+        "TO_DECIMAL"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "UTF-8 to Decimal"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("FROM_DECIMAL"))
+    }
+
+    fn get_input_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        RADIX_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        self.convert_bytes(input.as_bytes(), parameters)
+    }
+
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        encode_words(input, &DECIMAL, parameters)
+    }
+}
+
+pub struct OperationFromDecimal {}
+
+impl TextOperation for OperationFromDecimal {
+    fn get_id(&self) -> &'static str {
+        "FROM_DECIMAL"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Decimal to UTF-8"
+    }
+
+    fn get_inverse(&self) -> Option<String> {
+        Some(String::from("TO_DECIMAL"))
+    }
+
+    fn get_output_type(&self) -> DataType {
+        DataType::Bytes
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        RADIX_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        decode_words(input, &DECIMAL, parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    fn words(word_size: &str, byte_order: &str) -> OperationParameters {
+        OperationParameters::from_schema(RADIX_PARAMETERS)
+            .with("word_size", ParameterValue::Text(String::from(word_size)))
+            .with("byte_order", ParameterValue::Text(String::from(byte_order)))
+    }
+
+    #[test]
+    fn it_converts_bytes_to_binary_octal_and_decimal() {
+        let parameters = OperationParameters::from_schema(RADIX_PARAMETERS);
+
+        let binary = OperationToBinary {}.convert("AB\n", &parameters);
+        assert_eq!(
+            binary.unwrap().text_value.unwrap(),
+            "01000001 01000010 00001010"
+        );
+        let octal = OperationToOctal {}.convert("AB\n", &parameters);
+        assert_eq!(octal.unwrap().text_value.unwrap(), "101 102 012");
+        let decimal = OperationToDecimal {}.convert("AB\n", &parameters);
+        assert_eq!(decimal.unwrap().text_value.unwrap(), "65 66 10");
+    }
+
+    #[test]
+    fn it_converts_words_in_either_byte_order() {
+        let input = [0x12, 0x34, 0x56, 0x78];
+
+        let big = OperationToDecimal {}.convert_bytes(&input, &words("16-bit", "Big endian"));
+        assert_eq!(big.unwrap().text_value.unwrap(), "4660 22136");
+        let little = OperationToDecimal {}.convert_bytes(&input, &words("32-bit", "Little endian"));
+        assert_eq!(little.unwrap().text_value.unwrap(), "2018915346");
+
+        let parsed =
+            OperationFromDecimal {}.convert("2018915346", &words("32-bit", "Little endian"));
+        assert_eq!(parsed.unwrap().byte_value.unwrap(), input);
+    }
+
+    #[test]
+    fn it_rejects_incomplete_words() {
+        let result = OperationToOctal {}.convert("ABC", &words("16-bit", "Big endian"));
+        assert_eq!(result.unwrap_err().get_range(), Some(2..3));
+    }
+
+    #[test]
+    fn it_parses_binary_without_separators() {
+        let result = OperationFromBinary {}.convert(
+            "0100000101000010",
+            &OperationParameters::from_schema(RADIX_PARAMETERS),
+        );
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"AB");
+    }
+
+    #[test]
+    fn it_parses_values_split_by_the_separator() {
+        let parameters = OperationParameters::from_schema(RADIX_PARAMETERS)
+            .with("separator", ParameterValue::Text(String::from(", ")));
+
+        let result = OperationFromDecimal {}.convert("65, 66,67\n68", &parameters);
+        assert_eq!(result.unwrap().byte_value.unwrap(), b"ABCD");
+    }
+
+    #[test]
+    fn it_reports_values_too_large_for_the_word() {
+        let result = OperationFromDecimal {}.convert(
+            "65 256",
+            &OperationParameters::from_schema(RADIX_PARAMETERS),
+        );
+        assert_eq!(result.unwrap_err().get_range(), Some(3..6));
+    }
+
+    #[test]
+    fn it_rejects_characters_that_are_not_digits() {
+        let parameters = OperationParameters::from_schema(RADIX_PARAMETERS);
+
+        let result = OperationFromBinary {}.convert("€€€", &parameters);
+        assert_eq!(result.unwrap_err().get_range(), Some(0..1));
+
+        // 8 is the first character that is not an octal digit
+        let result = OperationFromOctal {}.convert("12345678é", &parameters);
+        assert_eq!(result.unwrap_err().get_range(), Some(7..8));
+
+        let result = OperationFromDecimal {}.convert("65 +66", &parameters);
+        assert_eq!(result.unwrap_err().get_range(), Some(3..4));
+    }
+}
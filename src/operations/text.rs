@@ -1,4 +1,6 @@
-use super::text_operation::{TextOperation, TextOperationError, TextOperationResult};
+use super::text_operation::{
+    OperationParameters, StreamConverter, TextOperation, TextOperationError, TextOperationResult,
+};
 
 pub struct OperationUpperCase {}
 
@@ -11,9 +13,17 @@ impl TextOperation for OperationUpperCase {
         "To upper case"
     }
 
-    fn convert(&self, input: &str) -> Result<TextOperationResult, TextOperationError> {
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
         Ok(TextOperationResult::with_string(input.to_uppercase()))
     }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(CaseConverter::new(str::to_uppercase)))
+    }
 }
 
 pub struct OperationLowerCase {}
@@ -27,9 +37,80 @@ impl TextOperation for OperationLowerCase {
         "To lower case"
     }
 
-    fn convert(&self, input: &str) -> Result<TextOperationResult, TextOperationError> {
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
         Ok(TextOperationResult::with_string(input.to_lowercase()))
     }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(CaseConverter::new(str::to_lowercase)))
+    }
+}
+
+/// Maps the case of UTF-8 text as it arrives. The text after the last space or digit is held
+/// back, as the case of a letter can depend on the letters around it like the final sigma,
+/// and a character split across chunks is held back with it.
+pub(super) struct CaseConverter {
+    map_case: fn(&str) -> String,
+    pending: Vec<u8>,
+    /// The offset of the first pending byte in the stream
+    offset: usize,
+}
+
+impl CaseConverter {
+    pub(super) fn new(map_case: fn(&str) -> String) -> Self {
+        CaseConverter {
+            map_case,
+            pending: vec![],
+            offset: 0,
+        }
+    }
+}
+
+impl StreamConverter for CaseConverter {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        self.pending.extend_from_slice(input);
+
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(TextOperationError::InvalidInputAtOffset(
+                    self.offset + e.valid_up_to(),
+                ))
+            }
+        };
+
+        // the bytes up to `valid` were checked above
+        let text = std::str::from_utf8(&self.pending[..valid]).unwrap_or_default();
+        let complete = text
+            .char_indices()
+            .rev()
+            .find(|(_, c)| is_word_boundary(*c))
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        output.extend_from_slice((self.map_case)(&text[..complete]).as_bytes());
+        self.pending.drain(..complete);
+        self.offset += complete;
+        Ok(())
+    }
+
+    fn finish(&mut self, output: &mut Vec<u8>) -> Result<(), TextOperationError> {
+        let text = std::str::from_utf8(&self.pending)
+            .map_err(|e| TextOperationError::InvalidInputAtOffset(self.offset + e.valid_up_to()))?;
+        output.extend_from_slice((self.map_case)(text).as_bytes());
+        self.offset += self.pending.len();
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// Characters that are neither letters nor ignored when looking at the letters around one,
+/// so no case mapping depends on what is on the other side of them
+fn is_word_boundary(c: char) -> bool {
+    c.is_whitespace() || c.is_ascii_digit()
 }
 
 #[cfg(test)]
@@ -37,12 +118,13 @@ mod test {
     use super::OperationLowerCase;
     use super::OperationUpperCase;
     use super::TextOperation;
+    use crate::operations::text_operation::OperationParameters;
 
     #[test]
     fn it_converts_to_upper_case() {
         let operation = OperationUpperCase {};
 
-        let result = operation.convert("aBcD");
+        let result = operation.convert("aBcD", &operation.default_parameters());
         assert_eq!(result.unwrap().text_value.unwrap(), "ABCD");
     }
 
@@ -50,7 +132,40 @@ mod test {
     fn it_converts_to_lower_case() {
         let operation = OperationLowerCase {};
 
-        let result = operation.convert("aBcD");
+        let result = operation.convert("aBcD", &operation.default_parameters());
         assert_eq!(result.unwrap().text_value.unwrap(), "abcd");
     }
+
+    #[test]
+    fn it_converts_characters_split_across_chunks() {
+        let mut stream = OperationUpperCase {}
+            .get_stream(&OperationParameters::default())
+            .unwrap();
+        let mut output: Vec<u8> = vec![];
+
+        // "é" is two bytes in UTF-8
+        stream.update(b"caf\xc3", &mut output).unwrap();
+        stream.update(b"\xa9!", &mut output).unwrap();
+        stream.finish(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "CAFÉ!");
+    }
+
+    #[test]
+    fn it_streams_the_final_sigma_like_in_memory() {
+        let operation = OperationLowerCase {};
+        let mut stream = operation
+            .get_stream(&OperationParameters::default())
+            .unwrap();
+        let mut output: Vec<u8> = vec![];
+
+        stream.update("ΟΔΟΣ ΟΔΟ".as_bytes(), &mut output).unwrap();
+        stream.update("Σ".as_bytes(), &mut output).unwrap();
+        stream.finish(&mut output).unwrap();
+
+        let result = operation.convert("ΟΔΟΣ ΟΔΟΣ", &operation.default_parameters());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            result.unwrap().text_value.unwrap()
+        );
+    }
 }
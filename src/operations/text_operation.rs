@@ -1,20 +1,167 @@
-use std::{error::Error, fmt::Display};
+use std::{collections::HashMap, error::Error, fmt::Display, ops::Range};
 
+use crate::fl;
+use serde::{Deserialize, Serialize};
+
+/// Errors raised by operations, offsets and ranges are byte positions in the operation input
 #[derive(Debug)]
 pub enum TextOperationError {
     InvalidInput(),
     InvalidInputError(String),
     InvalidInputAtOffset(usize),
+    InvalidInputInRange(Range<usize>, String),
+}
+
+impl TextOperationError {
+    /// The byte range of the input that caused the error, if it is known
+    pub fn get_range(&self) -> Option<Range<usize>> {
+        match self {
+            TextOperationError::InvalidInputAtOffset(offset) => Some(*offset..*offset + 1),
+            TextOperationError::InvalidInputInRange(range, _) => Some(range.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl Display for TextOperationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        let message = match self {
+            TextOperationError::InvalidInput() => fl!("error-invalid-input"),
+            TextOperationError::InvalidInputError(message) => {
+                fl!("error-invalid-input-message", message = message.as_str())
+            }
+            TextOperationError::InvalidInputAtOffset(offset) => {
+                fl!("error-invalid-input-at-offset", offset = offset.to_string())
+            }
+            TextOperationError::InvalidInputInRange(range, message) => fl!(
+                "error-invalid-input-in-range",
+                start = range.start.to_string(),
+                end = range.end.to_string(),
+                message = message.as_str()
+            ),
+        };
+        write!(f, "{}", message)
     }
 }
 
 impl Error for TextOperationError {}
 
+/// The kind of data an operation consumes or produces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    Text,
+    Bytes,
+}
+
+/// A value supplied for one of an operation's parameters, choices are stored as `Text`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ParameterValue {
+    Bool(bool),
+    Integer(i64),
+    Text(String),
+}
+
+/// The type of a parameter along with the value used when none is supplied
+#[derive(Debug, Clone, Copy)]
+pub enum ParameterKind {
+    Choice {
+        options: &'static [&'static str],
+        default: &'static str,
+    },
+    Bool {
+        default: bool,
+    },
+    Integer {
+        min: i64,
+        max: i64,
+        default: i64,
+    },
+    Text {
+        default: &'static str,
+    },
+}
+
+/// Describes a single option that can be configured on an operation
+#[derive(Debug, Clone, Copy)]
+pub struct OperationParameter {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub kind: ParameterKind,
+}
+
+impl OperationParameter {
+    pub fn default_value(&self) -> ParameterValue {
+        match self.kind {
+            ParameterKind::Choice { default, .. } => ParameterValue::Text(String::from(default)),
+            ParameterKind::Bool { default } => ParameterValue::Bool(default),
+            ParameterKind::Integer { default, .. } => ParameterValue::Integer(default),
+            ParameterKind::Text { default } => ParameterValue::Text(String::from(default)),
+        }
+    }
+}
+
+/// The parameter values passed to an operation when converting
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OperationParameters {
+    values: HashMap<String, ParameterValue>,
+}
+
+impl OperationParameters {
+    pub fn from_schema(schema: &[OperationParameter]) -> Self {
+        let mut parameters = OperationParameters::default();
+        for parameter in schema {
+            parameters.set(parameter.id, parameter.default_value());
+        }
+        parameters
+    }
+
+    pub fn with(mut self, id: &str, value: ParameterValue) -> Self {
+        self.set(id, value);
+        self
+    }
+
+    pub fn set(&mut self, id: &str, value: ParameterValue) {
+        self.values.insert(String::from(id), value);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ParameterValue> {
+        self.values.get(id)
+    }
+
+    /// Returns a copy of these parameters with every value in `overrides` replacing its own
+    pub fn merged(&self, overrides: &OperationParameters) -> OperationParameters {
+        let mut merged = self.clone();
+        for (id, value) in &overrides.values {
+            merged.values.insert(id.clone(), value.clone());
+        }
+        merged
+    }
+
+    pub fn get_bool(&self, id: &str) -> bool {
+        match self.values.get(id) {
+            Some(ParameterValue::Bool(value)) => *value,
+            _ => false,
+        }
+    }
+
+    pub fn get_integer(&self, id: &str) -> i64 {
+        match self.values.get(id) {
+            Some(ParameterValue::Integer(value)) => *value,
+            _ => 0,
+        }
+    }
+
+    pub fn get_text(&self, id: &str) -> &str {
+        match self.values.get(id) {
+            Some(ParameterValue::Text(value)) => value.as_str(),
+            _ => "",
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct TextOperationResult {
     pub text_value: Option<String>,
     pub byte_value: Option<Vec<u8>>,
@@ -34,14 +181,54 @@ impl TextOperationResult {
             byte_value: Some(bytes),
         }
     }
+
+    pub fn get_type(&self) -> DataType {
+        match self.byte_value {
+            Some(_) => DataType::Bytes,
+            None => DataType::Text,
+        }
+    }
+
+    /// The size of the value in bytes
+    pub fn get_size(&self) -> usize {
+        match (&self.byte_value, &self.text_value) {
+            (Some(bytes), _) => bytes.len(),
+            (None, Some(text)) => text.len(),
+            (None, None) => 0,
+        }
+    }
+
+    /// Renders at most `length` characters of the value the same way it is displayed as output
+    pub fn render_preview(&self, length: usize) -> String {
+        match (&self.byte_value, &self.text_value) {
+            (Some(bytes), _) => match std::str::from_utf8(bytes) {
+                Ok(text) => text.chars().take(length).collect(),
+                Err(_) => bytes
+                    .iter()
+                    .take(length / 2)
+                    .map(|b| format!("{:02x}", b))
+                    .collect(),
+            },
+            (None, Some(text)) => text.chars().take(length).collect(),
+            (None, None) => String::new(),
+        }
+    }
 }
 
 impl Into<String> for TextOperationResult {
+    /// Renders the result for display, bytes that are not valid UTF-8 are shown as hex
     fn into(self) -> String {
         match self.text_value {
             Some(text) => text,
             None => match self.byte_value {
-                Some(bytes) => bytes.iter().map(|b| format!("{:x}", b)).collect(),
+                Some(bytes) => match String::from_utf8(bytes) {
+                    Ok(text) => text,
+                    Err(e) => e
+                        .into_bytes()
+                        .iter()
+                        .map(|b| format!("{:02x}", b))
+                        .collect(),
+                },
                 None => String::from(""),
             },
         }
@@ -53,12 +240,39 @@ pub trait TextOperation {
 
     fn get_name(&self) -> &'static str;
 
-    fn convert(&self, input: &str) -> Result<TextOperationResult, TextOperationError>;
+    /// The type of data this operation works on, operations that consume bytes should
+    /// implement `convert_bytes` and have `convert` pass the UTF-8 bytes of the text through
+    fn get_input_type(&self) -> DataType {
+        DataType::Text
+    }
+
+    /// The type of data this operation returns in its `TextOperationResult`
+    fn get_output_type(&self) -> DataType {
+        DataType::Text
+    }
+
+    /// The options that can be configured for this operation
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        &[]
+    }
+
+    fn default_parameters(&self) -> OperationParameters {
+        OperationParameters::from_schema(self.get_parameters())
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError>;
 
-    fn convert_bytes(&self, input: &Vec<u8>) -> Result<TextOperationResult, TextOperationError> {
-        let maybe_input = String::from_utf8(input.clone());
-        match maybe_input {
-            Ok(input) => self.convert(input.as_str()),
+    fn convert_bytes(
+        &self,
+        input: &[u8],
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        match std::str::from_utf8(input) {
+            Ok(input) => self.convert(input, parameters),
             Err(_) => Err(TextOperationError::InvalidInput()),
         }
     }
@@ -66,4 +280,19 @@ pub trait TextOperation {
     fn get_inverse(&self) -> Option<String> {
         None
     }
+
+    /// Returns a converter that processes the input in chunks, for operations that can handle
+    /// input larger than memory. The parameters have the defaults already applied.
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        None
+    }
+}
+
+/// Converts input that arrives in chunks, keeping whatever state is needed between chunks.
+/// Offsets in errors are counted from the start of the whole stream.
+pub trait StreamConverter {
+    fn update(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), TextOperationError>;
+
+    /// Converts anything still buffered once the end of the input has been reached
+    fn finish(&mut self, output: &mut Vec<u8>) -> Result<(), TextOperationError>;
 }
@@ -1,11 +1,43 @@
+use super::alphabets::{
+    OperationFromBraille, OperationFromMorse, OperationFromNato, OperationToBraille,
+    OperationToMorse, OperationToNato,
+};
+use super::base_encodings::{
+    OperationDecodeBase32, OperationDecodeBase45, OperationDecodeBase58, OperationDecodeBase85,
+    OperationEncodeBase32, OperationEncodeBase45, OperationEncodeBase58, OperationEncodeBase85,
+};
+use super::charset::{OperationDecodeCharset, OperationEncodeCharset};
+use super::cipher::{
+    OperationAtbash, OperationCaesarDecrypt, OperationCaesarEncrypt, OperationRot13,
+    OperationRot47, OperationVigenereDecrypt, OperationVigenereEncrypt, OperationXor,
+    OperationXorBruteForce,
+};
+use super::compression::{OperationGzipCompress, OperationGzipDecompress};
+use super::data_format::{OperationToJson, OperationToRon, OperationToToml, OperationToYaml};
+use super::escape::{OperationEscapeString, OperationUnescapeString};
 use super::hex::OperationFromHex;
 use super::hex::{OperationToHex, OperationToLongHex};
+use super::hex_dump::{OperationFromHexDump, OperationToHexDump};
+use super::json::{
+    OperationJsonBeautify, OperationJsonMinify, OperationJsonSortKeys, OperationJsonValidate,
+};
+use super::json_query::OperationJsonQuery;
 use super::none::OperationNone;
+use super::radix::{
+    OperationFromBinary, OperationFromDecimal, OperationFromOctal, OperationToBinary,
+    OperationToDecimal, OperationToOctal,
+};
 use super::text::{OperationLowerCase, OperationUpperCase};
-pub use super::text_operation::{TextOperation, TextOperationError, TextOperationResult};
+pub use super::text_operation::{
+    DataType, OperationParameter, OperationParameters, ParameterKind, ParameterValue,
+    TextOperation, TextOperationError, TextOperationResult,
+};
+use super::unicode::{OperationCaseFold, OperationInspectUnicode, OperationNormalizeUnicode};
 use super::web::{
-    OperationDecodeBase64, OperationDecodeURL, OperationEncodeBase64, OperationEncodeBase64UrlSafe,
-    OperationEncodeURL,
+    OperationDecodeBase64, OperationDecodeEncodedWord, OperationDecodeJwt,
+    OperationDecodeQuotedPrintable, OperationDecodeURL, OperationDomainToAscii,
+    OperationDomainToUnicode, OperationEncodeBase64, OperationEncodeBase64UrlSafe,
+    OperationEncodeEncodedWord, OperationEncodeQuotedPrintable, OperationEncodeURL,
 };
 use std::sync::OnceLock;
 use std::{collections::HashMap, sync::Arc};
@@ -36,6 +68,62 @@ impl TextOperations {
         add_operation(Box::new(OperationFromHex {}), &mut operations);
         add_operation(Box::new(OperationToHex {}), &mut operations);
         add_operation(Box::new(OperationToLongHex {}), &mut operations);
+        add_operation(Box::new(OperationToHexDump {}), &mut operations);
+        add_operation(Box::new(OperationFromHexDump {}), &mut operations);
+        add_operation(Box::new(OperationToBinary {}), &mut operations);
+        add_operation(Box::new(OperationFromBinary {}), &mut operations);
+        add_operation(Box::new(OperationToOctal {}), &mut operations);
+        add_operation(Box::new(OperationFromOctal {}), &mut operations);
+        add_operation(Box::new(OperationToDecimal {}), &mut operations);
+        add_operation(Box::new(OperationFromDecimal {}), &mut operations);
+        add_operation(Box::new(OperationEncodeCharset {}), &mut operations);
+        add_operation(Box::new(OperationDecodeCharset {}), &mut operations);
+        add_operation(Box::new(OperationInspectUnicode {}), &mut operations);
+        add_operation(Box::new(OperationNormalizeUnicode {}), &mut operations);
+        add_operation(Box::new(OperationCaseFold {}), &mut operations);
+        add_operation(Box::new(OperationEscapeString {}), &mut operations);
+        add_operation(Box::new(OperationUnescapeString {}), &mut operations);
+        add_operation(Box::new(OperationEncodeQuotedPrintable {}), &mut operations);
+        add_operation(Box::new(OperationDecodeQuotedPrintable {}), &mut operations);
+        add_operation(Box::new(OperationEncodeEncodedWord {}), &mut operations);
+        add_operation(Box::new(OperationDecodeEncodedWord {}), &mut operations);
+        add_operation(Box::new(OperationDomainToAscii {}), &mut operations);
+        add_operation(Box::new(OperationDomainToUnicode {}), &mut operations);
+        add_operation(Box::new(OperationDecodeJwt {}), &mut operations);
+        add_operation(Box::new(OperationGzipCompress {}), &mut operations);
+        add_operation(Box::new(OperationGzipDecompress {}), &mut operations);
+        add_operation(Box::new(OperationEncodeBase32 {}), &mut operations);
+        add_operation(Box::new(OperationDecodeBase32 {}), &mut operations);
+        add_operation(Box::new(OperationEncodeBase58 {}), &mut operations);
+        add_operation(Box::new(OperationDecodeBase58 {}), &mut operations);
+        add_operation(Box::new(OperationEncodeBase85 {}), &mut operations);
+        add_operation(Box::new(OperationDecodeBase85 {}), &mut operations);
+        add_operation(Box::new(OperationEncodeBase45 {}), &mut operations);
+        add_operation(Box::new(OperationDecodeBase45 {}), &mut operations);
+        add_operation(Box::new(OperationRot13 {}), &mut operations);
+        add_operation(Box::new(OperationRot47 {}), &mut operations);
+        add_operation(Box::new(OperationCaesarEncrypt {}), &mut operations);
+        add_operation(Box::new(OperationCaesarDecrypt {}), &mut operations);
+        add_operation(Box::new(OperationAtbash {}), &mut operations);
+        add_operation(Box::new(OperationVigenereEncrypt {}), &mut operations);
+        add_operation(Box::new(OperationVigenereDecrypt {}), &mut operations);
+        add_operation(Box::new(OperationXor {}), &mut operations);
+        add_operation(Box::new(OperationXorBruteForce {}), &mut operations);
+        add_operation(Box::new(OperationToMorse {}), &mut operations);
+        add_operation(Box::new(OperationFromMorse {}), &mut operations);
+        add_operation(Box::new(OperationToNato {}), &mut operations);
+        add_operation(Box::new(OperationFromNato {}), &mut operations);
+        add_operation(Box::new(OperationToBraille {}), &mut operations);
+        add_operation(Box::new(OperationFromBraille {}), &mut operations);
+        add_operation(Box::new(OperationJsonBeautify {}), &mut operations);
+        add_operation(Box::new(OperationJsonMinify {}), &mut operations);
+        add_operation(Box::new(OperationJsonSortKeys {}), &mut operations);
+        add_operation(Box::new(OperationJsonValidate {}), &mut operations);
+        add_operation(Box::new(OperationJsonQuery {}), &mut operations);
+        add_operation(Box::new(OperationToJson {}), &mut operations);
+        add_operation(Box::new(OperationToYaml {}), &mut operations);
+        add_operation(Box::new(OperationToToml {}), &mut operations);
+        add_operation(Box::new(OperationToRon {}), &mut operations);
 
         let all: Vec<String> = operations
             .iter()
@@ -87,7 +175,7 @@ mod tests {
         let text_operations = TextOperations::get_instance();
         let operations = text_operations.get_operations();
 
-        assert_eq!(operations.len(), 10);
+        assert_eq!(operations.len(), 67);
     }
 
     #[test]
@@ -109,7 +197,7 @@ mod tests {
     fn it_provides_an_operation_with_no_transformation() {
         let text_operations = TextOperations::get_instance();
         let operation = text_operations.get_noop();
-        let result = operation.convert("abc");
+        let result = operation.convert("abc", &operation.default_parameters());
         assert_eq!(result.unwrap().text_value.unwrap(), "abc");
     }
 }
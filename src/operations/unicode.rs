@@ -0,0 +1,242 @@
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_normalization::UnicodeNormalization;
+use unicode_script::UnicodeScript;
+
+use super::text::CaseConverter;
+use super::text_operation::{
+    OperationParameter, OperationParameters, ParameterKind, StreamConverter, TextOperation,
+    TextOperationError, TextOperationResult,
+};
+
+const FORM_NFC: &str = "NFC";
+const FORM_NFD: &str = "NFD";
+const FORM_NFKC: &str = "NFKC";
+const FORM_NFKD: &str = "NFKD";
+
+const NORMALIZE_PARAMETERS: &[OperationParameter] = &[OperationParameter {
+    id: "form",
+    name: "Form",
+    kind: ParameterKind::Choice {
+        options: &[FORM_NFC, FORM_NFD, FORM_NFKC, FORM_NFKD],
+        default: FORM_NFC,
+    },
+}];
+
+const INSPECTOR_COLUMNS: [&str; 7] = [
+    "Code point",
+    "Char",
+    "Name",
+    "Category",
+    "Script",
+    "UTF-8",
+    "UTF-16",
+];
+
+pub struct OperationInspectUnicode {}
+
+impl TextOperation for OperationInspectUnicode {
+    fn get_id(&self) -> &'static str {
+        "INSPECT_UNICODE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Unicode Characters"
+    }
+
+    /// Lists every character of the input as a row of a table
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let mut rows: Vec<[String; 7]> = vec![INSPECTOR_COLUMNS.map(String::from)];
+        rows.extend(input.chars().map(describe_char));
+
+        let mut widths = [0; 7];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut table = String::new();
+        for row in &rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:width$}", cell))
+                .collect();
+            table.push_str(cells.join("  ").trim_end());
+            table.push('\n');
+        }
+
+        Ok(TextOperationResult::with_string(table))
+    }
+}
+
+fn describe_char(c: char) -> [String; 7] {
+    let category = get_general_category(c);
+    let shown = match category {
+        // marks are drawn on a dotted circle so they do not combine with the column before
+        GeneralCategory::NonspacingMark
+        | GeneralCategory::SpacingMark
+        | GeneralCategory::EnclosingMark => format!("\u{25cc}{}", c),
+        GeneralCategory::Control
+        | GeneralCategory::Format
+        | GeneralCategory::LineSeparator
+        | GeneralCategory::ParagraphSeparator
+        | GeneralCategory::SpaceSeparator
+        | GeneralCategory::Unassigned => String::new(),
+        _ => String::from(c),
+    };
+    let name = match (unicode_names2::name(c), category) {
+        (Some(name), _) => name.to_string(),
+        (None, GeneralCategory::Control) => String::from("<control>"),
+        (None, GeneralCategory::PrivateUse) => String::from("<private use>"),
+        (None, GeneralCategory::Surrogate) => String::from("<surrogate>"),
+        (None, _) => String::from("<unassigned>"),
+    };
+
+    let mut utf16 = [0u16; 2];
+    let utf8: Vec<String> = c
+        .to_string()
+        .bytes()
+        .map(|b| format!("{:02X}", b))
+        .collect();
+    let utf16: Vec<String> = c
+        .encode_utf16(&mut utf16)
+        .iter()
+        .map(|unit| format!("{:04X}", unit))
+        .collect();
+
+    [
+        format!("U+{:04X}", c as u32),
+        shown,
+        name,
+        String::from(category.abbreviation()),
+        String::from(c.script().full_name()),
+        utf8.join(" "),
+        utf16.join(" "),
+    ]
+}
+
+pub struct OperationNormalizeUnicode {}
+
+impl TextOperation for OperationNormalizeUnicode {
+    fn get_id(&self) -> &'static str {
+        "NORMALIZE_UNICODE"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Unicode Normalization"
+    }
+
+    fn get_parameters(&self) -> &'static [OperationParameter] {
+        NORMALIZE_PARAMETERS
+    }
+
+    fn convert(
+        &self,
+        input: &str,
+        parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        let normalized: String = match parameters.get_text("form") {
+            FORM_NFD => input.nfd().collect(),
+            FORM_NFKC => input.nfkc().collect(),
+            FORM_NFKD => input.nfkd().collect(),
+            _ => input.nfc().collect(),
+        };
+        Ok(TextOperationResult::with_string(normalized))
+    }
+}
+
+pub struct OperationCaseFold {}
+
+impl TextOperation for OperationCaseFold {
+    fn get_id(&self) -> &'static str {
+        "CASE_FOLD"
+    }
+
+    fn get_name(&self) -> &'static str {
+        "Case fold"
+    }
+
+    /// Applies full Unicode case folding, so text that only differs in case compares equal
+    fn convert(
+        &self,
+        input: &str,
+        _parameters: &OperationParameters,
+    ) -> Result<TextOperationResult, TextOperationError> {
+        Ok(TextOperationResult::with_string(
+            caseless::default_case_fold_str(input),
+        ))
+    }
+
+    fn get_stream(&self, _parameters: &OperationParameters) -> Option<Box<dyn StreamConverter>> {
+        Some(Box::new(CaseConverter::new(
+            caseless::default_case_fold_str,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::text_operation::ParameterValue;
+
+    #[test]
+    fn it_lists_the_characters_of_the_input() {
+        let operation = OperationInspectUnicode {};
+
+        let result = operation.convert("Aé\u{301}", &operation.default_parameters());
+        let table = result.unwrap().text_value.unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("Code point  Char  Name"));
+        assert!(lines[1].starts_with("U+0041      A     LATIN CAPITAL LETTER A"));
+        assert!(lines[1].ends_with("Lu        Latin      41     0041"));
+        assert!(lines[2].contains("LATIN SMALL LETTER E WITH ACUTE"));
+        assert!(lines[2].ends_with("C3 A9  00E9"));
+        assert!(lines[3].contains("\u{25cc}\u{301}"));
+        assert!(lines[3].contains("COMBINING ACUTE ACCENT"));
+        assert!(lines[3].ends_with("Mn        Inherited  CC 81  0301"));
+    }
+
+    #[test]
+    fn it_shows_utf16_surrogate_pairs_and_control_characters() {
+        let operation = OperationInspectUnicode {};
+
+        let result = operation.convert("😀\n", &operation.default_parameters());
+        let table = result.unwrap().text_value.unwrap();
+
+        assert!(table.contains("F0 9F 98 80  D83D DE00"));
+        assert!(table.contains("U+000A            <control>"));
+    }
+
+    #[test]
+    fn it_normalizes_to_each_form() {
+        let operation = OperationNormalizeUnicode {};
+
+        for (form, expected) in [
+            ("NFC", "é\u{fb01}"),
+            ("NFD", "e\u{301}\u{fb01}"),
+            ("NFKC", "éfi"),
+            ("NFKD", "e\u{301}fi"),
+        ] {
+            let parameters = operation
+                .default_parameters()
+                .with("form", ParameterValue::Text(String::from(form)));
+            let result = operation.convert("e\u{301}\u{fb01}", &parameters);
+            assert_eq!(result.unwrap().text_value.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn it_folds_case() {
+        let operation = OperationCaseFold {};
+
+        let result = operation.convert("Straße ΣΊΣΥΦΟΣ", &operation.default_parameters());
+        assert_eq!(result.unwrap().text_value.unwrap(), "strasse σίσυφοσ");
+    }
+}